@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
 
 pub struct ActionsPlugin;
 
@@ -13,12 +14,48 @@ impl Plugin for ActionsPlugin {
     }
 }
 
-#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect, Serialize, Deserialize)]
 pub enum GameAction {
     // Movement
     Left,
     Right,
     Brake,
+    Jump,
+    Duck,
     // Ui
     Continue,
+    Pause,
+}
+
+// rebindable keyboard bindings, mouse and gamepad bindings stay fixed
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeyBindings(pub Vec<(GameAction, KeyCode)>);
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        use GameAction::*;
+        Self(vec![
+            // Movement
+            (Left, KeyCode::ArrowLeft),
+            (Right, KeyCode::ArrowRight),
+            (Brake, KeyCode::ArrowDown),
+            (Jump, KeyCode::ArrowUp),
+            (Duck, KeyCode::ShiftLeft),
+            // Ui
+            (Continue, KeyCode::Space),
+            (Pause, KeyCode::Escape),
+        ])
+    }
+}
+
+impl KeyBindings {
+    // actions added after the bindings were saved fall back to their defaults
+    pub fn with_defaults(mut self) -> Self {
+        for (action, key) in Self::default().0 {
+            if !self.0.iter().any(|(bound, _)| *bound == action) {
+                self.0.push((action, key));
+            }
+        }
+        self
+    }
 }
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+pub struct SpriteAnimationPlugin;
+
+impl Plugin for SpriteAnimationPlugin {
+    fn name(&self) -> &str {
+        "Sprite Animation Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, animate_atlases);
+    }
+}
+
+// loops a sprite's atlas index through first..=last at a fixed rate
+#[derive(Component)]
+pub struct AtlasAnimation {
+    pub first: usize,
+    pub last: usize,
+    timer: Timer,
+}
+
+impl AtlasAnimation {
+    // `frames` frames starting at `first`
+    pub fn looping(first: usize, frames: usize, fps: f32) -> Self {
+        Self {
+            first,
+            last: first + frames.saturating_sub(1),
+            timer: Timer::from_seconds(1.0 / fps, TimerMode::Repeating),
+        }
+    }
+}
+
+fn animate_atlases(time: Res<Time>, mut query: Query<(&mut AtlasAnimation, &mut Sprite)>) {
+    for (mut animation, mut sprite) in &mut query {
+        // single frame animations have nothing to step through
+        if animation.first == animation.last {
+            continue;
+        }
+
+        animation.timer.tick(time.delta());
+        let steps = animation.timer.times_finished_this_tick() as usize;
+        if steps == 0 {
+            continue;
+        }
+
+        let Some(atlas) = &mut sprite.texture_atlas else {
+            continue;
+        };
+
+        let len = animation.last - animation.first + 1;
+        let current = atlas.index.clamp(animation.first, animation.last) - animation.first;
+        atlas.index = animation.first + (current + steps) % len;
+    }
+}
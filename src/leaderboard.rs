@@ -0,0 +1,241 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GameState, Resolution,
+    date::Date,
+    loading::FontAssets,
+    player::PlayerStats,
+    save::SaveBackend,
+    ui::{ButtonColors, ChangeState, UiColor},
+};
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn name(&self) -> &str {
+        "Leaderboard Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Leaderboard>()
+            .init_resource::<LastRunRank>()
+            .add_systems(PreStartup, load_leaderboard)
+            .add_systems(OnEnter(GameState::Leaderboard), spawn_leaderboard);
+    }
+}
+
+const LEADERBOARD_VERSION: u32 = 1;
+const LEADERBOARD_KEY: &str = "leaderboard.ron";
+const LEADERBOARD_SIZE: usize = 10;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub score: u32,
+    pub coins: u32,
+    pub time_secs: f32,
+    pub distance: f32,
+    pub stats: PlayerStats,
+    pub date: Date,
+}
+
+// top runs, best first
+#[derive(Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Leaderboard {
+    pub version: u32,
+    pub entries: Vec<RunRecord>,
+}
+
+impl Default for Leaderboard {
+    fn default() -> Self {
+        Self {
+            version: LEADERBOARD_VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl Leaderboard {
+    // returns the rank the run placed at, if it made the table
+    pub fn submit(&mut self, record: RunRecord) -> Option<usize> {
+        let rank = self
+            .entries
+            .iter()
+            .position(|entry| record.score > entry.score)
+            .unwrap_or(self.entries.len());
+        if rank >= LEADERBOARD_SIZE {
+            return None;
+        }
+        self.entries.insert(rank, record);
+        self.entries.truncate(LEADERBOARD_SIZE);
+        Some(rank)
+    }
+
+    pub fn store(&self, backend: &SaveBackend) {
+        backend.store(LEADERBOARD_KEY, self);
+    }
+}
+
+// where the most recent run placed, highlighted on the leaderboard screen
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct LastRunRank(pub Option<usize>);
+
+fn load_leaderboard(backend: Res<SaveBackend>, mut leaderboard: ResMut<Leaderboard>) {
+    let Some(loaded) = backend.load::<Leaderboard>(LEADERBOARD_KEY) else {
+        return;
+    };
+
+    if loaded.version > LEADERBOARD_VERSION {
+        warn!(
+            "Leaderboard version {} is newer than supported version {}, ignoring it",
+            loaded.version, LEADERBOARD_VERSION
+        );
+        return;
+    }
+
+    *leaderboard = Leaderboard {
+        version: LEADERBOARD_VERSION,
+        ..loaded
+    };
+}
+
+pub(crate) fn format_time(secs: f32) -> String {
+    format!("{:02}:{:05.2}", (secs / 60.0) as u32, secs % 60.0)
+}
+
+fn spawn_leaderboard(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    fonts: Res<FontAssets>,
+    leaderboard: Res<Leaderboard>,
+    last_run_rank: Res<LastRunRank>,
+) {
+    let s = resolution.ui_scale();
+
+    let btn_w = 140.0 * s;
+    let border = 4.0 * s;
+    let pad_x = 16.0 * s;
+    let pad_y = 8.0 * s;
+    let row_gap = 20.0 * s;
+    let col_gap = 24.0 * s;
+
+    let title_font = 48.0 * s;
+    let row_font = 20.0 * s;
+    let btn_font = btn_w / 7.0;
+
+    let button_colors = ButtonColors::default();
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Leaderboard),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(row_gap),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("High Scores"),
+                TextFont {
+                    font: fonts.tiny5.clone(),
+                    font_size: title_font,
+                    ..default()
+                },
+                TextColor(UiColor::Darkest.color()),
+            ));
+
+            parent
+                .spawn((
+                    Node {
+                        display: Display::Grid,
+                        grid_template_columns: RepeatedGridTrack::auto(7),
+                        column_gap: Val::Px(col_gap),
+                        padding: UiRect::all(Val::Px(pad_x)),
+                        border: UiRect::all(Val::Px(border)),
+                        ..default()
+                    },
+                    BackgroundColor(UiColor::Light.color()),
+                    BorderColor::all(UiColor::Darkest.color()),
+                ))
+                .with_children(|table| {
+                    let mut cell = |text: String, highlight: bool| {
+                        let color = if highlight {
+                            UiColor::Lighter.color()
+                        } else {
+                            Color::NONE
+                        };
+                        table.spawn((
+                            Text::new(text),
+                            TextFont {
+                                font: fonts.tiny5.clone(),
+                                font_size: row_font,
+                                ..default()
+                            },
+                            TextColor(UiColor::Darkest.color()),
+                            BackgroundColor(color),
+                        ));
+                    };
+
+                    for header in ["#", "Score", "Dist", "Time", "Coins", "A/D/S/L", "Date"] {
+                        cell(header.to_string(), false);
+                    }
+
+                    if leaderboard.entries.is_empty() {
+                        cell("-".to_string(), false);
+                        cell("No runs yet".to_string(), false);
+                        for _ in 0..5 {
+                            cell(String::new(), false);
+                        }
+                    }
+
+                    for (rank, entry) in leaderboard.entries.iter().enumerate() {
+                        let highlight = **last_run_rank == Some(rank);
+                        let stats = &entry.stats;
+                        cell(format!("{}", rank + 1), highlight);
+                        cell(entry.score.to_string(), highlight);
+                        cell(format!("{:.0}m", entry.distance), highlight);
+                        cell(format_time(entry.time_secs), highlight);
+                        cell(entry.coins.to_string(), highlight);
+                        cell(
+                            format!(
+                                "{}/{}/{}/{}",
+                                stats.attack, stats.defence, stats.speed, stats.luck
+                            ),
+                            highlight,
+                        );
+                        cell(entry.date.to_string(), highlight);
+                    }
+                });
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(btn_w),
+                        border: UiRect::all(Val::Px(border)),
+                        padding: UiRect::axes(Val::Px(pad_x), Val::Px(pad_y)),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    BackgroundColor(UiColor::Lighter.color()),
+                    BorderColor::all(UiColor::Darkest.color()),
+                    ChangeState(GameState::Menu),
+                    button_colors.clone(),
+                ))
+                .with_child((
+                    Text::new("Back"),
+                    TextFont {
+                        font: fonts.tiny5.clone(),
+                        font_size: btn_font,
+                        ..default()
+                    },
+                    TextColor(UiColor::Darkest.color()),
+                ));
+        });
+}
@@ -1,25 +1,34 @@
 #![allow(clippy::type_complexity)]
 
 mod actions;
+mod animation;
 mod audio;
+mod data;
+mod date;
+mod leaderboard;
 mod loading;
 mod luge;
 mod menu;
 mod player;
+mod save;
 mod settings;
 mod ui;
 
 use crate::actions::ActionsPlugin;
+use crate::animation::SpriteAnimationPlugin;
 use crate::audio::InternalAudioPlugin;
+use crate::leaderboard::LeaderboardPlugin;
 use crate::loading::LoadingPlugin;
 use crate::luge::LugePlugin;
 use crate::menu::MenuPlugin;
 use crate::player::PlayerPlugin;
+use crate::save::SavePlugin;
 use crate::settings::SettingsPlugin;
 use crate::ui::{UiColor, UiPlugin};
 
 use bevy::app::App;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 //use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
 
 //#[cfg(debug_assertions)]
@@ -32,6 +41,7 @@ pub enum GameState {
     Playing,
     Menu,
     Settings,
+    Leaderboard,
 }
 
 #[derive(SubStates, Clone, PartialEq, Eq, Hash, Debug, Default)]
@@ -40,9 +50,19 @@ pub enum LugeState {
     #[default]
     Loadout,
     Launched,
+    Results,
 }
 
-#[derive(Resource, Default, Clone, Copy)]
+#[derive(SubStates, Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[source(LugeState = LugeState::Launched)]
+pub enum RunState {
+    #[default]
+    Running,
+    Paused,
+    Settings,
+}
+
+#[derive(Resource, Default, Clone, Copy, Serialize, Deserialize)]
 pub enum Resolution {
     Sd,
     #[default]
@@ -109,10 +129,14 @@ impl Plugin for GamePlugin {
             ActionsPlugin,
             LugePlugin,
             SettingsPlugin,
+            SavePlugin,
+            LeaderboardPlugin,
+            SpriteAnimationPlugin,
         ));
         // Initialize gamestates
         app.init_state::<GameState>();
         app.add_sub_state::<LugeState>();
+        app.add_sub_state::<RunState>();
         // Spawn camera
         app.add_systems(Startup, spawn_camera);
         // External plugins
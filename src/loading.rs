@@ -1,4 +1,5 @@
 use crate::GameState;
+use crate::luge::{DifficultyCurve, SpawnPatterns};
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 //use bevy_kira_audio::AudioSource;
@@ -13,7 +14,8 @@ impl Plugin for LoadingPlugin {
                 .load_collection::<AudioAssets>()
                 .load_collection::<TextureAssets>()
                 .load_collection::<FontAssets>()
-                .load_collection::<SpriteAssets>(),
+                .load_collection::<SpriteAssets>()
+                .load_collection::<DataAssets>(),
         );
     }
 }
@@ -48,11 +50,20 @@ pub struct SpriteAssets {
     #[asset(path = "sprites/slick_coins_atlas.png")]
     pub coins: Handle<Image>,
 
-    #[allow(dead_code)]
     #[asset(path = "sprites/enemies_atlas.png")]
     pub enemies: Handle<Image>,
 }
 
+// designer-tunable gameplay data
+#[derive(AssetCollection, Resource)]
+pub struct DataAssets {
+    #[asset(path = "data/default.curve.ron")]
+    pub difficulty: Handle<DifficultyCurve>,
+
+    #[asset(path = "data/default.patterns.ron")]
+    pub patterns: Handle<SpawnPatterns>,
+}
+
 #[derive(AssetCollection, Resource)]
 pub struct FontAssets {
     #[asset(path = "fonts/Tiny5-Regular.ttf")]
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    date::Date,
+    player::PlayerStats,
+    save::{SaveData, SaveRequested},
+};
+
+use super::results::RunSummary;
+use super::score::RunScore;
+
+// everyone plays the daily course with the same sled, upgrades don't count
+pub(super) const DAILY_STATS: PlayerStats = PlayerStats {
+    attack: 3,
+    defence: 3,
+    speed: 3,
+    luck: 3,
+};
+
+// present while the player is in a daily challenge session
+#[derive(Resource)]
+pub(crate) struct DailyChallenge {
+    pub date: Date,
+}
+
+impl DailyChallenge {
+    // the date itself as YYYYMMDD, the same course for everyone on the same day
+    pub fn seed(&self) -> u64 {
+        self.date.year as u64 * 10_000 + self.date.month as u64 * 100 + self.date.day as u64
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub(crate) struct DailyRecord {
+    pub last_attempt: Option<Date>,
+    pub last_score: u32,
+    pub best_score: u32,
+}
+
+impl DailyRecord {
+    pub fn attempted(&self, date: Date) -> bool {
+        self.last_attempt == Some(date)
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct StartDaily;
+
+pub(super) fn start_daily(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<StartDaily>)>,
+) {
+    if interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        let daily = DailyChallenge {
+            date: Date::today(),
+        };
+        info!(
+            "Starting daily challenge {} with seed {}",
+            daily.date,
+            daily.seed()
+        );
+        commands.insert_resource(daily);
+    }
+}
+
+pub(super) fn end_daily(mut commands: Commands) {
+    commands.remove_resource::<DailyChallenge>();
+}
+
+// only the first run of the day is scored, later ones are practice
+pub(super) fn record_daily(
+    mut commands: Commands,
+    daily: Res<DailyChallenge>,
+    score: Res<RunScore>,
+    mut save: ResMut<SaveData>,
+    mut summary: ResMut<RunSummary>,
+) {
+    if save.daily.attempted(daily.date) {
+        info!("Daily {} already attempted, run not scored", daily.date);
+        return;
+    }
+
+    let record = &mut save.daily;
+    record.last_attempt = Some(daily.date);
+    record.last_score = **score;
+    summary.new_record = **score > record.best_score;
+    record.best_score = record.best_score.max(**score);
+    commands.insert_resource(SaveRequested);
+    info!("Daily {} scored {} points", daily.date, **score);
+}
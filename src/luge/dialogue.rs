@@ -1,7 +1,12 @@
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::ActionState;
+use serde::{Deserialize, Serialize};
 
-use crate::{actions::GameAction, player::Player};
+use crate::{
+    actions::GameAction,
+    player::Player,
+    save::{SaveData, SaveRequested},
+};
 
 #[derive(Component)]
 pub(crate) struct RickDialogue;
@@ -53,7 +58,14 @@ impl RickLines {
             },
             Scene {
                 id: SceneId::Shop,
-                lines: vec![],
+                lines: vec![
+                    "Now dis here's da shop. Hit da plus next to a stat \
+                    and I'll tune up yer sled. For a small fee, of course."
+                        .to_string(),
+                    "Every level costs a little more than da last. \
+                    Quality ain't cheap, capice?"
+                        .to_string(),
+                ],
                 completed: false,
             },
         ])
@@ -70,6 +82,20 @@ impl RickLines {
     pub(crate) fn get_line(&self, id: SceneId, index: usize) -> Option<&str> {
         self.get_scene(id)?.lines.get(index).map(|s| s.as_str())
     }
+
+    fn is_completed(&self, id: SceneId) -> bool {
+        self.get_scene(id).is_some_and(|s| s.completed)
+    }
+
+    // marks the scene completed and records it in the save
+    fn complete(&mut self, id: SceneId, save: &mut SaveData) {
+        if let Some(scene) = self.get_scene_mut(id) {
+            scene.completed = true;
+        }
+        if !save.completed_scenes.contains(&id) {
+            save.completed_scenes.push(id);
+        }
+    }
 }
 
 struct Scene {
@@ -78,7 +104,7 @@ struct Scene {
     completed: bool,
 }
 
-#[derive(PartialEq, Copy, Clone)]
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub(crate) enum SceneId {
     Intro,
     Shop,
@@ -93,7 +119,37 @@ impl SceneId {
     }
 }
 
+// skips scenes Rick already went through in a previous session
+pub(super) fn restore_dialogue(
+    save: Res<SaveData>,
+    mut rick_lines: ResMut<RickLines>,
+    mut dialogue_state: ResMut<DialogueState>,
+) {
+    for id in &save.completed_scenes {
+        if let Some(scene) = rick_lines.get_scene_mut(*id) {
+            scene.completed = true;
+        }
+    }
+
+    let mut scene = Some(SceneId::Intro);
+    while let Some(id) = scene
+        && rick_lines.is_completed(id)
+    {
+        scene = id.next();
+    }
+
+    match scene {
+        Some(id) => {
+            dialogue_state.current_scene = id;
+            dialogue_state.line_index = 0;
+        }
+        None => dialogue_state.waiting_for_input = false,
+    }
+}
+
 pub(super) fn advance_dialogue(
+    mut commands: Commands,
+    mut save: ResMut<SaveData>,
     mut dialogue_state: ResMut<DialogueState>,
     mut rick_lines: ResMut<RickLines>,
     mut rick_text: Single<&mut Text, With<RickDialogue>>,
@@ -112,9 +168,8 @@ pub(super) fn advance_dialogue(
             rick_text.0 = line.to_string();
         } else {
             // Mark current scene completed
-            if let Some(scene) = rick_lines.get_scene_mut(dialogue_state.current_scene) {
-                scene.completed = true;
-            }
+            rick_lines.complete(dialogue_state.current_scene, &mut save);
+            commands.insert_resource(SaveRequested);
 
             // Advance to next scene or finish dialogue
             if let Some(next_scene) = dialogue_state.current_scene.next() {
@@ -124,9 +179,7 @@ pub(super) fn advance_dialogue(
                     rick_text.0 = line.to_string();
                     return;
                 }
-                if let Some(scene) = rick_lines.get_scene_mut(next_scene) {
-                    scene.completed = true;
-                }
+                rick_lines.complete(next_scene, &mut save);
             }
             dialogue_state.waiting_for_input = false;
             rick_text.0 = String::new();
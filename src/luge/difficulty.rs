@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::loading::DataAssets;
+
+use super::RunDistance;
+
+// one point on the difficulty curve
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub(crate) struct DifficultyKey {
+    // meters slid
+    pub distance: f32,
+    // seconds between spawns before luck shortens it
+    pub spawn_interval: f32,
+    pub enemy_chance: f64,
+    pub obstacle_chance: f64,
+    // share of coins that float in the air
+    pub air_coin_chance: f64,
+    // chance a spawn places a whole formation instead of a single occupant
+    #[serde(default)]
+    pub pattern_chance: f64,
+    // share of non-hazard spawns that are boost pads or speed pickups
+    #[serde(default)]
+    pub boost_chance: f64,
+}
+
+impl DifficultyKey {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            distance: self.distance.lerp(other.distance, t),
+            spawn_interval: self.spawn_interval.lerp(other.spawn_interval, t),
+            enemy_chance: self.enemy_chance.lerp(other.enemy_chance, t as f64),
+            obstacle_chance: self.obstacle_chance.lerp(other.obstacle_chance, t as f64),
+            air_coin_chance: self.air_coin_chance.lerp(other.air_coin_chance, t as f64),
+            pattern_chance: self.pattern_chance.lerp(other.pattern_chance, t as f64),
+            boost_chance: self.boost_chance.lerp(other.boost_chance, t as f64),
+        }
+    }
+}
+
+// the flat difficulty the game had before the curve, used until the data file loads
+impl Default for DifficultyKey {
+    fn default() -> Self {
+        Self {
+            distance: 0.0,
+            spawn_interval: 2.5,
+            enemy_chance: 0.3,
+            obstacle_chance: 0.15,
+            air_coin_chance: 0.25,
+            pattern_chance: 0.0,
+            boost_chance: 0.0,
+        }
+    }
+}
+
+// keys sorted by distance, see assets/data/default.curve.ron
+#[derive(Asset, TypePath, Deserialize)]
+pub(crate) struct DifficultyCurve {
+    pub keys: Vec<DifficultyKey>,
+}
+
+impl DifficultyCurve {
+    pub fn sample(&self, distance: f32) -> DifficultyKey {
+        let Some(first) = self.keys.first() else {
+            return DifficultyKey::default();
+        };
+        let next = self.keys.partition_point(|key| key.distance <= distance);
+        match (self.keys.get(next.wrapping_sub(1)), self.keys.get(next)) {
+            (Some(a), Some(b)) => {
+                let t = (distance - a.distance) / (b.distance - a.distance);
+                a.lerp(b, t)
+            }
+            (Some(last), None) => *last,
+            _ => *first,
+        }
+    }
+}
+
+// the curve sampled at the current distance, read by the spawner
+#[derive(Resource, Default, Deref)]
+pub(super) struct Difficulty(DifficultyKey);
+
+pub(super) fn update_difficulty(
+    distance: Res<RunDistance>,
+    data: Option<Res<DataAssets>>,
+    curves: Res<Assets<DifficultyCurve>>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    let Some(curve) = data.and_then(|data| curves.get(&data.difficulty)) else {
+        return;
+    };
+    difficulty.0 = curve.sample(**distance);
+}
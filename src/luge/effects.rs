@@ -0,0 +1,143 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{LugeState, Resolution, loading::FontAssets, ui::UiColor};
+
+const TEXT_SECS: f32 = 0.8;
+const PARTICLE_SECS: f32 = 0.4;
+// world pixels per second before resolution scaling
+const TEXT_RISE: f32 = 40.0;
+const PARTICLE_SPEED: f32 = 60.0;
+const PARTICLE_SIZE: f32 = 3.0;
+const SPEED_LINE_SECS: f32 = 0.35;
+const SPEED_LINE_SPEED: f32 = 900.0;
+const SPEED_LINE_SIZE: Vec2 = Vec2::new(1.0, 40.0);
+// lines per meter per second gained
+const SPEED_LINES_PER_SPEED: f32 = 2.0;
+
+// sent by the coin collision check, the effects are spawned from it outside the fixed step
+#[derive(Message)]
+pub(super) struct CoinCollected {
+    pub value: u32,
+    pub position: Vec3,
+}
+
+// sent when Luigee hits a boost pad or speed pickup
+#[derive(Message)]
+pub(super) struct SpeedBoosted {
+    pub speed: f32,
+}
+
+// despawns its entity when the timer runs out, fading it on the way
+#[derive(Component)]
+pub(super) struct Lifetime(Timer);
+
+// velocity of an effect entity
+#[derive(Component)]
+pub(super) struct Drift(Vec3);
+
+// bigger coins get a brighter, bigger burst
+fn burst_for(value: u32) -> (UiColor, usize) {
+    match value {
+        0..5 => (UiColor::Light, 6),
+        5..25 => (UiColor::Lighter, 10),
+        _ => (UiColor::Lightest, 16),
+    }
+}
+
+pub(super) fn spawn_pickup_effects(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    fonts: Res<FontAssets>,
+    mut collected: MessageReader<CoinCollected>,
+) {
+    let scale = resolution.scale();
+
+    for coin in collected.read() {
+        let (color, count) = burst_for(coin.value);
+        let position = coin.position.with_z(3.0);
+
+        commands.spawn((
+            Text2d::new(format!("+{}", coin.value)),
+            TextFont {
+                font: fonts.tiny5.clone(),
+                font_size: 10.0 * scale,
+                ..default()
+            },
+            TextColor(color.color()),
+            Transform::from_translation(position),
+            Lifetime(Timer::from_seconds(TEXT_SECS, TimerMode::Once)),
+            Drift(Vec3::Y * TEXT_RISE * scale),
+            DespawnOnExit(LugeState::Launched),
+        ));
+
+        // evenly spaced so effects never touch the run's rng
+        for i in 0..count {
+            let angle = TAU * i as f32 / count as f32;
+            commands.spawn((
+                Sprite::from_color(color.color(), Vec2::splat(PARTICLE_SIZE * scale)),
+                Transform::from_translation(position),
+                Lifetime(Timer::from_seconds(PARTICLE_SECS, TimerMode::Once)),
+                Drift(Vec3::new(angle.cos(), angle.sin(), 0.0) * PARTICLE_SPEED * scale),
+                DespawnOnExit(LugeState::Launched),
+            ));
+        }
+    }
+}
+
+// streaks rushing past down the whole screen, more of them for a bigger boost
+pub(super) fn spawn_speed_lines(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    mut boosted: MessageReader<SpeedBoosted>,
+) {
+    let scale = resolution.scale();
+    let size = resolution.vec2();
+
+    for boost in boosted.read() {
+        let count = (boost.speed * SPEED_LINES_PER_SPEED).round() as usize;
+        // spread with the golden ratio so the lines look scattered without the run's rng
+        for i in 0..count {
+            let x = ((i as f32 * 0.618).fract() - 0.5) * size.x;
+            let y = ((i as f32 * 0.382).fract() - 0.5) * size.y;
+            commands.spawn((
+                Sprite::from_color(UiColor::Lightest.color(), SPEED_LINE_SIZE * scale),
+                Transform::from_xyz(x, y, 3.0),
+                Lifetime(Timer::from_seconds(SPEED_LINE_SECS, TimerMode::Once)),
+                Drift(Vec3::NEG_Y * SPEED_LINE_SPEED * scale),
+                DespawnOnExit(LugeState::Launched),
+            ));
+        }
+    }
+}
+
+pub(super) fn update_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut Lifetime,
+        &Drift,
+        &mut Transform,
+        Option<&mut TextColor>,
+        Option<&mut Sprite>,
+    )>,
+) {
+    for (entity, mut lifetime, drift, mut transform, text_color, sprite) in &mut query {
+        lifetime.0.tick(time.delta());
+        if lifetime.0.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation += drift.0 * time.delta_secs();
+        let alpha = 1.0 - lifetime.0.fraction();
+        if let Some(mut text_color) = text_color {
+            text_color.0.set_alpha(alpha);
+        }
+        if let Some(mut sprite) = sprite {
+            sprite.color.set_alpha(alpha);
+        }
+    }
+}
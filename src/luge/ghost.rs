@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{LugeState, Resolution, loading::SpriteAssets, save::SaveBackend};
+
+use super::daily::DailyChallenge;
+use super::score::RunScore;
+use super::{LaneLocation, Lanes, LuigeeSprite, PIXELS_PER_METER, PlayerLane, RunDistance};
+
+const GHOST_VERSION: u32 = 1;
+const GHOST_KEY: &str = "ghost.ron";
+const GHOST_ALPHA: f32 = 0.4;
+
+// where Luigee was on one fixed tick of a run
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(super) struct GhostFrame {
+    pub lane: LaneLocation,
+    pub distance: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct GhostRun {
+    pub version: u32,
+    pub score: u32,
+    pub frames: Vec<GhostFrame>,
+}
+
+// the highest scoring run so far, raced against on every launch
+#[derive(Resource, Default)]
+pub(super) struct BestGhost(Option<GhostRun>);
+
+#[derive(Resource, Default)]
+pub(super) struct GhostRecording(Vec<GhostFrame>);
+
+// meters the live run is ahead of the ghost, negative when behind
+#[derive(Resource, Default, Deref, DerefMut)]
+pub(super) struct GhostGap(Option<f32>);
+
+#[derive(Component)]
+pub(super) struct GhostSprite;
+
+pub(super) fn load_ghost(backend: Res<SaveBackend>, mut best: ResMut<BestGhost>) {
+    let Some(ghost) = backend.load::<GhostRun>(GHOST_KEY) else {
+        return;
+    };
+
+    if ghost.version > GHOST_VERSION {
+        warn!(
+            "Ghost version {} is newer than supported version {}, ignoring it",
+            ghost.version, GHOST_VERSION
+        );
+        return;
+    }
+
+    best.0 = Some(ghost);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn spawn_ghost(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    sprites: Res<SpriteAssets>,
+    best: Res<BestGhost>,
+    daily: Option<Res<DailyChallenge>>,
+    mut recording: ResMut<GhostRecording>,
+    mut gap: ResMut<GhostGap>,
+    luigee: Single<&Transform, With<LuigeeSprite>>,
+) {
+    recording.0.clear();
+    **gap = None;
+
+    // the best run was on a different course than the daily one
+    if best.0.is_none() || daily.is_some() {
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Ghost Luigee"),
+        GhostSprite,
+        Sprite {
+            color: Color::WHITE.with_alpha(GHOST_ALPHA),
+            // the ghost holds the first frame of the ride cycle
+            rect: Some(Rect::new(0.0, 0.0, 32.0, 64.0)),
+            ..Sprite::from_image(sprites.luigee.clone())
+        },
+        // just behind the live sprite so Luigee stays readable when they overlap
+        Transform {
+            translation: luigee.translation.with_z(luigee.translation.z - 0.1),
+            scale: Vec3::splat(resolution.scale()),
+            ..default()
+        },
+        DespawnOnExit(LugeState::Launched),
+    ));
+}
+
+pub(super) fn record_ghost_frame(
+    player_lane: Res<PlayerLane>,
+    distance: Res<RunDistance>,
+    mut recording: ResMut<GhostRecording>,
+) {
+    recording.0.push(GhostFrame {
+        lane: **player_lane,
+        distance: **distance,
+    });
+}
+
+// the ghost sits on the same tick as the live run, offset up or down the track by the gap
+#[allow(clippy::too_many_arguments)]
+pub(super) fn follow_ghost(
+    resolution: Res<Resolution>,
+    lanes: Res<Lanes>,
+    best: Res<BestGhost>,
+    distance: Res<RunDistance>,
+    recording: Res<GhostRecording>,
+    mut gap: ResMut<GhostGap>,
+    luigee: Single<&Transform, (With<LuigeeSprite>, Without<GhostSprite>)>,
+    mut ghost: Single<&mut Transform, With<GhostSprite>>,
+) {
+    let Some(best) = &best.0 else {
+        return;
+    };
+    let tick = recording.0.len().saturating_sub(1);
+    // a ghost that already stopped stays on its last frame
+    let Some(frame) = best.frames.get(tick).or(best.frames.last()) else {
+        return;
+    };
+
+    let ahead = **distance - frame.distance;
+    **gap = Some(ahead);
+
+    ghost.translation.x = lanes.x_for(frame.lane);
+    ghost.translation.y = luigee.translation.y - ahead * PIXELS_PER_METER * resolution.scale();
+}
+
+pub(super) fn save_ghost(
+    backend: Res<SaveBackend>,
+    score: Res<RunScore>,
+    mut best: ResMut<BestGhost>,
+    mut recording: ResMut<GhostRecording>,
+) {
+    if best.0.as_ref().is_some_and(|ghost| ghost.score >= **score) {
+        return;
+    }
+
+    let ghost = GhostRun {
+        version: GHOST_VERSION,
+        score: **score,
+        frames: std::mem::take(&mut recording.0),
+    };
+    backend.store(GHOST_KEY, &ghost);
+    best.0 = Some(ghost);
+    info!("New best run, ghost saved");
+}
@@ -1,18 +1,44 @@
+mod daily;
 mod dialogue;
+mod difficulty;
+mod effects;
+mod ghost;
+mod patterns;
+mod pause;
+mod physics;
+mod powerups;
+mod replay;
+mod results;
+mod rng;
+mod score;
+mod shop;
 mod spawner;
+mod stance;
 mod ui;
 
+use std::f32::consts::PI;
+
 use bevy::{prelude::*, time::Stopwatch};
 use leafwing_input_manager::prelude::ActionState;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    GameState, LugeState, Resolution,
+    GameState, LugeState, Resolution, RunState,
     actions::GameAction,
+    animation::AtlasAnimation,
+    data::RonAssetPlugin,
     loading::SpriteAssets,
     player::{Player, PlayerStats},
+    settings::Settings,
 };
 
 use dialogue::{DialogueState, RickLines};
+use physics::ScrollSpeed;
+
+pub(crate) use daily::{DailyRecord, StartDaily};
+pub(crate) use dialogue::SceneId;
+pub(crate) use difficulty::DifficultyCurve;
+pub(crate) use patterns::SpawnPatterns;
 
 pub struct LugePlugin;
 
@@ -22,6 +48,11 @@ impl Plugin for LugePlugin {
     }
 
     fn build(&self, app: &mut App) {
+        app.add_systems(Startup, (dialogue::restore_dialogue, ghost::load_ghost));
+        app.add_plugins((
+            RonAssetPlugin::<DifficultyCurve>::new(&["curve.ron"]),
+            RonAssetPlugin::<SpawnPatterns>::new(&["patterns.ron"]),
+        ));
         app.add_systems(
             OnEnter(GameState::Playing),
             (
@@ -32,18 +63,35 @@ impl Plugin for LugePlugin {
                 update_lanes,
                 set_input_cooldown,
                 spawner::init_coin_atlas,
-                spawner::reset_player_coins,
+                spawner::init_enemy_atlas,
             ),
         )
-        .add_systems(OnEnter(LugeState::Loadout), reset_luge)
+        .add_systems(OnExit(GameState::Playing), daily::end_daily)
+        .add_systems(Update, daily::start_daily.run_if(in_state(GameState::Menu)))
+        .add_systems(Update, patterns::build_pattern_table)
+        .add_systems(
+            OnEnter(LugeState::Loadout),
+            (reset_luge, stance::reset_stance),
+        )
         .add_systems(
             OnEnter(LugeState::Launched),
             (
-                reset_run_timer,
-                reset_scroll_speed,
-                spawner::init_spawn_timer,
-                spawner::spawn_initial_coin,
-            ),
+                (rng::seed_run_rng, init_run_stats, replay::start_recording),
+                (
+                    reset_run_timer,
+                    score::reset_run_score,
+                    results::reset_run_summary,
+                    physics::launch_sled,
+                    powerups::reset_power_ups,
+                    reset_durability,
+                    spawner::reset_player_coins,
+                    ui::hide_loadout_buttons,
+                    spawner::init_spawn_timer,
+                    spawner::spawn_initial_coin,
+                    ghost::spawn_ghost,
+                ),
+            )
+                .chain(),
         )
         .add_systems(
             Update,
@@ -51,38 +99,148 @@ impl Plugin for LugePlugin {
                 consume_stale_input.run_if(resource_exists::<InputCooldown>),
                 dialogue::advance_dialogue,
                 ui::toggle_launch_button,
+                // the daily challenge has a fixed loadout and course
+                (
+                    ui::toggle_loadout_buttons,
+                    shop::buy_stat,
+                    replay::start_replay,
+                    ui::edit_seed,
+                )
+                    .run_if(not(resource_exists::<daily::DailyChallenge>)),
+                ui::update_stat_texts,
+                ui::update_wallet_text,
             )
                 .chain()
                 .run_if(in_state(LugeState::Loadout)),
         )
+        // the simulation runs on a fixed timestep so replays play back identically
         .add_systems(
-            Update,
+            FixedUpdate,
             (
+                replay::sample_input,
                 tick_run_timer,
-                ui::update_run_timer_text,
-                decelerate_luigee,
+                physics::integrate_sled,
                 move_luigee,
-                update_luigee_sprite,
-                scroll_lanes,
+                slide_luigee,
+                stance::update_stance,
+                (
+                    scroll_lanes,
+                    score::update_run_score,
+                    ghost::record_ghost_frame,
+                    ghost::follow_ghost.run_if(any_with_component::<ghost::GhostSprite>),
+                )
+                    .chain(),
                 (
-                    spawner::spawn_coins,
+                    difficulty::update_difficulty,
+                    powerups::tick_power_ups,
+                    spawner::spawn_occupants,
                     spawner::scroll_occupants,
+                    powerups::magnet_coins,
                     spawner::collect_coins,
+                    spawner::miss_coins,
+                    powerups::collect_power_ups,
+                    spawner::hit_enemies,
+                    spawner::hit_obstacles,
+                    spawner::ride_boosts,
                     spawner::despawn_offscreen,
                 )
                     .chain(),
+                check_durability,
+            )
+                .chain()
+                .run_if(in_state(RunState::Running).and(not(resource_exists::<RunFinished>))),
+        )
+        .add_systems(
+            Update,
+            (
+                stance::show_stance,
+                (
+                    effects::spawn_pickup_effects,
+                    effects::spawn_speed_lines,
+                    effects::update_effects,
+                )
+                    .chain()
+                    .run_if(in_state(RunState::Running)),
+                ui::update_run_timer_text,
+                ui::update_distance_text,
                 ui::update_coin_count_text,
+                ui::update_combo_text,
+                ui::update_power_up_texts,
+                ui::update_durability_text,
+                ui::update_seed_text,
+                ui::update_ghost_gap_text,
+                ui::update_daily_text,
             )
                 .run_if(in_state(LugeState::Launched)),
         )
-        .add_systems(OnExit(LugeState::Launched), spawner::cleanup_spawner)
+        .add_systems(
+            Update,
+            (
+                pause::toggle_pause,
+                pause::abandon_run.run_if(in_state(RunState::Paused)),
+                pause::adjust_volume.run_if(in_state(RunState::Settings)),
+            )
+                .run_if(in_state(LugeState::Launched)),
+        )
+        .add_systems(OnEnter(LugeState::Results), results::spawn_results)
+        .add_systems(
+            Update,
+            results::continue_from_results.run_if(in_state(LugeState::Results)),
+        )
+        .add_systems(OnEnter(RunState::Paused), pause::spawn_pause_menu)
+        .add_systems(OnEnter(RunState::Settings), pause::spawn_pause_settings)
+        .add_systems(
+            OnExit(LugeState::Launched),
+            (
+                spawner::cleanup_spawner,
+                // daily coins still bank, only the leaderboard and ghost are skipped
+                spawner::deposit_player_coins.run_if(
+                    not(resource_exists::<replay::ReplayPlayback>)
+                        .and(not(resource_exists::<AbandonedRun>)),
+                ),
+                (score::record_run, ghost::save_ghost).run_if(
+                    not(resource_exists::<replay::ReplayPlayback>)
+                        .and(not(resource_exists::<daily::DailyChallenge>))
+                        .and(not(resource_exists::<AbandonedRun>)),
+                ),
+                daily::record_daily.run_if(
+                    resource_exists::<daily::DailyChallenge>
+                        .and(not(resource_exists::<replay::ReplayPlayback>))
+                        .and(not(resource_exists::<AbandonedRun>)),
+                ),
+                replay::finish_replay,
+                clear_run_finished,
+            )
+                .chain(),
+        )
+        .add_message::<effects::CoinCollected>()
+        .add_message::<effects::SpeedBoosted>()
+        .insert_resource(Time::<Fixed>::from_hz(replay::TIMESTEP_HZ))
         .insert_resource(Lanes::default())
         .insert_resource(PlayerLane::default())
+        .insert_resource(LaneShift::default())
+        .insert_resource(stance::Stance::default())
         .insert_resource(ScrollSpeed::default())
+        .insert_resource(physics::Slope::default())
+        .insert_resource(powerups::ActivePowerUps::default())
+        .insert_resource(Durability::default())
         .insert_resource(DialogueState::default())
         .insert_resource(RunTimer::default())
+        .insert_resource(RunDistance::default())
+        .insert_resource(RunStats::default())
+        .insert_resource(ghost::BestGhost::default())
+        .insert_resource(ghost::GhostRecording::default())
+        .insert_resource(ghost::GhostGap::default())
+        .insert_resource(replay::LugeInput::default())
+        .insert_resource(rng::RunRng::default())
+        .insert_resource(rng::RunSeed::default())
+        .insert_resource(score::RunScore::default())
+        .insert_resource(difficulty::Difficulty::default())
+        .insert_resource(patterns::PatternTable::default())
+        .insert_resource(results::RunSummary::default())
         .insert_resource(RickLines::init())
-        .insert_resource(spawner::PlayerCoins::default());
+        .insert_resource(spawner::PlayerCoins::default())
+        .insert_resource(spawner::Combo::default());
     }
 }
 
@@ -96,13 +254,51 @@ struct LaneSprite;
 #[derive(Resource, Default, Deref, DerefMut)]
 struct RunTimer(Stopwatch);
 
-fn spawn_luigee(mut commands: Commands, resolution: Res<Resolution>, sprites: Res<SpriteAssets>) {
+// meters slid this run
+#[derive(Resource, Default, Deref, DerefMut)]
+struct RunDistance(f32);
+
+// stats the current run is played with, the shop can't change them mid-run
+#[derive(Resource, Default, Deref, DerefMut)]
+struct RunStats(PlayerStats);
+
+// set once a run has ended so leftover fixed ticks in that frame don't keep simulating
+#[derive(Resource)]
+struct RunFinished;
+
+// set when the player quits from the pause menu, nothing from that run is kept
+#[derive(Resource)]
+struct AbandonedRun;
+
+// sprite pixels (before resolution scaling) per meter of track
+const PIXELS_PER_METER: f32 = 8.0;
+
+// ride cycle frames in the luigee sheet
+const LUIGEE_FRAMES: usize = 2;
+const RIDE_FPS: f32 = 8.0;
+
+fn spawn_luigee(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    settings: Res<Settings>,
+    sprites: Res<SpriteAssets>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
     let y = -(resolution.vec2().y / 3.0);
+    let layout =
+        TextureAtlasLayout::from_grid(UVec2::new(32, 64), LUIGEE_FRAMES as u32, 1, None, None);
 
     commands.spawn((
         Player,
-        Player::default_input_map(),
-        Sprite::from_image(sprites.luigee.clone()),
+        Player::input_map(&settings.bindings),
+        Sprite::from_atlas_image(
+            sprites.luigee.clone(),
+            TextureAtlas {
+                layout: layouts.add(layout),
+                index: 0,
+            },
+        ),
+        AtlasAnimation::looping(0, LUIGEE_FRAMES, RIDE_FPS),
         Transform {
             translation: Vec3::new(0.0, y, 0.0),
             scale: Vec3::splat(resolution.scale()),
@@ -137,12 +333,33 @@ fn spawn_lanes(mut commands: Commands, sprites: Res<SpriteAssets>, resolution: R
     ));
 }
 
-#[derive(Resource, Deref, DerefMut, Copy, Clone)]
-pub struct ScrollSpeed(pub f32);
+const BASE_DURABILITY: i32 = 10;
+
+#[derive(Resource, Copy, Clone)]
+pub struct Durability {
+    pub current: i32,
+    pub max: i32,
+}
 
-impl Default for ScrollSpeed {
+impl Default for Durability {
     fn default() -> Self {
-        Self(1000.0)
+        Self {
+            current: BASE_DURABILITY,
+            max: BASE_DURABILITY,
+        }
+    }
+}
+
+impl Durability {
+    // defence soaks up part of every hit, but a hit always hurts a little
+    pub fn take_hit(&mut self, damage: i32, defence: i32) -> i32 {
+        let taken = (damage - defence).max(1);
+        self.current = (self.current - taken).max(0);
+        taken
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.current == 0
     }
 }
 
@@ -181,7 +398,7 @@ pub struct Lane {
     pub x: f32,
 }
 
-#[derive(Default, Copy, Clone, Debug, PartialEq)]
+#[derive(Default, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum LaneLocation {
     Left,
     #[default]
@@ -190,6 +407,18 @@ pub enum LaneLocation {
 }
 
 impl LaneLocation {
+    pub const ALL: [Self; 3] = [Self::Left, Self::Center, Self::Right];
+
+    // swaps left and right
+    pub fn mirrored(&self) -> Self {
+        use LaneLocation::*;
+        match self {
+            Left => Right,
+            Center => Center,
+            Right => Left,
+        }
+    }
+
     pub fn shift_left(&self) -> Self {
         use LaneLocation::*;
         match self {
@@ -207,47 +436,96 @@ impl LaneLocation {
             Right => Right,
         }
     }
+
+    // knocked into a neighbouring lane, the center lane can go either way
+    pub fn bounce(&self, to_left: bool) -> Self {
+        use LaneLocation::*;
+        match self {
+            Left | Right => Center,
+            Center if to_left => Left,
+            Center => Right,
+        }
+    }
 }
 
 fn update_lanes(mut lanes: ResMut<Lanes>, resolution: Res<Resolution>) {
     *lanes = Lanes::init(resolution);
 }
 
-fn move_luigee(
-    mut player_lane: ResMut<PlayerLane>,
-    action_state: Single<&ActionState<GameAction>, With<Player>>,
-) {
-    if action_state.just_pressed(&GameAction::Left) {
+fn move_luigee(mut player_lane: ResMut<PlayerLane>, input: Res<replay::LugeInput>) {
+    if input.left {
         info!("Luge Action Left");
         **player_lane = player_lane.shift_left();
     }
 
-    if action_state.just_pressed(&GameAction::Right) {
+    if input.right {
         info!("Luge Action Right");
         **player_lane = player_lane.shift_right();
     }
 }
 
-fn update_luigee_sprite(
-    player_lane: Res<PlayerLane>,
+const LANE_SHIFT_SECS: f32 = 0.3;
+const MIN_LANE_SHIFT_SECS: f32 = 0.08;
+// radians Luigee leans into a lane change at its midpoint
+const MAX_TILT: f32 = 0.3;
+
+// the lane change in progress, Luigee eases from `from` to the current lane
+#[derive(Resource, Default)]
+struct LaneShift {
+    from: f32,
+    elapsed: f32,
+    duration: f32,
+}
+
+fn slide_luigee(
+    time: Res<Time>,
+    run_stats: Res<RunStats>,
     lanes: Res<Lanes>,
+    player_lane: Res<PlayerLane>,
+    mut shift: ResMut<LaneShift>,
     mut transform: Single<&mut Transform, With<LuigeeSprite>>,
 ) {
+    // a new lane change starts from wherever Luigee is, even mid-transition
     if player_lane.is_changed() {
-        transform.translation.x = lanes.x_for(**player_lane);
-        info!("Moved to {:?}", **player_lane);
+        *shift = LaneShift {
+            from: transform.translation.x,
+            elapsed: 0.0,
+            duration: (LANE_SHIFT_SECS / (1.0 + run_stats.speed as f32 * 0.1))
+                .max(MIN_LANE_SHIFT_SECS),
+        };
+        info!("Moving to {:?}", **player_lane);
+    }
+
+    let target = lanes.x_for(**player_lane);
+    shift.elapsed = (shift.elapsed + time.delta_secs()).min(shift.duration);
+    let t = if shift.duration > 0.0 {
+        shift.elapsed / shift.duration
+    } else {
+        1.0
     };
+    let eased = t * t * (3.0 - 2.0 * t);
+    transform.translation.x = shift.from.lerp(target, eased);
+
+    let direction = target - shift.from;
+    let tilt = if direction == 0.0 {
+        0.0
+    } else {
+        -direction.signum() * MAX_TILT * (PI * t).sin()
+    };
+    transform.rotation = Quat::from_rotation_z(tilt);
 }
 
 fn scroll_lanes(
     time: Res<Time>,
-    player_stats: Res<PlayerStats>,
     scroll_speed: Res<ScrollSpeed>,
     resolution: Res<Resolution>,
+    mut distance: ResMut<RunDistance>,
+    mut summary: ResMut<results::RunSummary>,
     mut query_lane_sprites: Query<&mut Transform, With<LaneSprite>>,
 ) {
-    let speed = **scroll_speed * (player_stats.speed as f32 / 10.0);
-    let delta = speed * time.delta_secs();
+    **distance += **scroll_speed * time.delta_secs();
+    summary.max_speed = summary.max_speed.max(**scroll_speed);
+    let delta = scroll_speed.pixels_per_sec(&resolution) * time.delta_secs();
     for mut transform in query_lane_sprites.iter_mut() {
         transform.translation.y -= delta;
 
@@ -297,40 +575,57 @@ fn reset_run_timer(mut timer: ResMut<RunTimer>) {
     timer.0.reset();
 }
 
-const BASE_DECELERATION: f32 = 50.0;
+fn check_durability(
+    mut commands: Commands,
+    durability: Res<Durability>,
+    mut next_state: ResMut<NextState<LugeState>>,
+) {
+    if durability.is_changed() && durability.is_broken() {
+        info!("Luigee's sled broke");
+        finish_run(&mut commands, &mut next_state);
+    }
+}
 
-fn decelerate_luigee(
-    time: Res<Time>,
+fn finish_run(commands: &mut Commands, next_state: &mut NextState<LugeState>) {
+    commands.insert_resource(RunFinished);
+    next_state.set(LugeState::Results);
+}
+
+fn clear_run_finished(mut commands: Commands) {
+    commands.remove_resource::<RunFinished>();
+    commands.remove_resource::<AbandonedRun>();
+}
+
+fn init_run_stats(
     player_stats: Res<PlayerStats>,
-    mut scroll_speed: ResMut<ScrollSpeed>,
-    mut next_state: ResMut<NextState<LugeState>>,
-    action_state: Single<&ActionState<GameAction>, With<Player>>,
+    playback: Option<Res<replay::ReplayPlayback>>,
+    daily: Option<Res<daily::DailyChallenge>>,
+    mut run_stats: ResMut<RunStats>,
 ) {
-    let braking = if action_state.pressed(&GameAction::Brake) {
-        3.0
+    **run_stats = if let Some(playback) = playback {
+        playback.replay.stats.clone()
+    } else if daily.is_some() {
+        daily::DAILY_STATS
     } else {
-        1.0
+        player_stats.clone()
     };
-    let decel = BASE_DECELERATION / player_stats.speed as f32 * braking;
-    **scroll_speed = (**scroll_speed - decel * time.delta_secs()).max(0.0);
-
-    if **scroll_speed == 0.0 {
-        next_state.set(LugeState::Loadout);
-    }
 }
 
-fn reset_scroll_speed(mut scroll_speed: ResMut<ScrollSpeed>) {
-    *scroll_speed = ScrollSpeed::default();
+fn reset_durability(mut durability: ResMut<Durability>) {
+    *durability = Durability::default();
 }
 
 fn reset_luge(
     resolution: Res<Resolution>,
     mut player_lane: ResMut<PlayerLane>,
+    mut shift: ResMut<LaneShift>,
     mut luigee: Single<&mut Transform, With<LuigeeSprite>>,
     mut lanes: Query<&mut Transform, (With<LaneSprite>, Without<LuigeeSprite>)>,
 ) {
     **player_lane = LaneLocation::default();
+    *shift = LaneShift::default();
     luigee.translation.x = 0.0;
+    luigee.rotation = Quat::IDENTITY;
 
     let offsets = [0.0, 360.0 * resolution.scale()];
     for (i, mut transform) in lanes.iter_mut().enumerate() {
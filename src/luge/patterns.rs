@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::loading::DataAssets;
+
+use super::rng::RunRng;
+use super::spawner::{Boost, Obstacle, OccupantKind};
+use super::stance::Stance;
+
+// one formation as written in the data file, rows are listed in the order they
+// reach Luigee and read left, center, right:
+// '.' empty, 'c' coin, 'a' air coin, 'e' enemy, 'l' low obstacle, 'h' high obstacle,
+// 'b' boost pad, 's' speed pickup
+#[derive(Deserialize)]
+pub(crate) struct PatternDef {
+    pub name: String,
+    pub weight: f32,
+    // meters slid before the pattern can show up
+    #[serde(default)]
+    pub min_distance: f32,
+    pub rows: Vec<String>,
+}
+
+// see assets/data/default.patterns.ron
+#[derive(Asset, TypePath, Deserialize)]
+pub(crate) struct SpawnPatterns {
+    pub patterns: Vec<PatternDef>,
+}
+
+pub(super) type PatternRow = [Option<OccupantKind>; 3];
+
+pub(super) struct SpawnPattern {
+    pub name: String,
+    pub weight: f32,
+    pub min_distance: f32,
+    pub rows: Vec<PatternRow>,
+}
+
+// the patterns that passed validation, picked from by weight
+#[derive(Resource, Default)]
+pub(super) struct PatternTable(Vec<SpawnPattern>);
+
+impl PatternTable {
+    pub fn pick(&self, distance: f32, rng: &mut RunRng) -> Option<&SpawnPattern> {
+        let unlocked = || self.0.iter().filter(|p| p.min_distance <= distance);
+        let total: f32 = unlocked().map(|p| p.weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut roll = rng.random_range(0.0..total);
+        for pattern in unlocked() {
+            if roll < pattern.weight {
+                return Some(pattern);
+            }
+            roll -= pattern.weight;
+        }
+        None
+    }
+}
+
+fn parse_cell(cell: char) -> Result<Option<OccupantKind>, String> {
+    let kind = match cell {
+        '.' => None,
+        'c' => Some(OccupantKind::Coin),
+        'a' => Some(OccupantKind::AirCoin),
+        'e' => Some(OccupantKind::Enemy),
+        'l' => Some(OccupantKind::Obstacle(Obstacle::Low)),
+        'h' => Some(OccupantKind::Obstacle(Obstacle::High)),
+        'b' => Some(OccupantKind::Boost(Boost::Pad)),
+        's' => Some(OccupantKind::Boost(Boost::Pickup)),
+        other => return Err(format!("unknown cell '{other}'")),
+    };
+    Ok(kind)
+}
+
+fn parse_row(row: &str) -> Result<PatternRow, String> {
+    let cells = row.chars().map(parse_cell).collect::<Result<Vec<_>, _>>()?;
+    cells
+        .try_into()
+        .map_err(|_| format!("row \"{row}\" doesn't have 3 lanes"))
+}
+
+// every stance Luigee can take through a row
+const STANCES: [Stance; 3] = [Stance::Riding, Stance::Airborne(0.0), Stance::Crouched(0.0)];
+
+// whether one stance gets Luigee past all of these cells at once
+fn passable(cells: &[Option<OccupantKind>]) -> bool {
+    STANCES
+        .into_iter()
+        .any(|stance| cells.iter().flatten().all(|kind| !kind.blocks(stance)))
+}
+
+// Luigee needs two rows to move over one lane and is in reach of both lanes for the row in
+// between, returns the first row no free lane leads into
+fn first_unreachable_row(rows: &[PatternRow]) -> Option<usize> {
+    // the track before the pattern is open, so it can be entered from any lane
+    let mut two_back = [true; 3];
+    let mut one_back = [true; 3];
+    for (i, row) in rows.iter().enumerate() {
+        let mut reachable = [false; 3];
+        for to in 0..3 {
+            if !passable(&[row[to]]) {
+                continue;
+            }
+            let shifted = i > 0
+                && [to.wrapping_sub(1), to + 1].into_iter().any(|from| {
+                    from < 3 && two_back[from] && passable(&[rows[i - 1][from], rows[i - 1][to]])
+                });
+            reachable[to] = one_back[to] || shifted;
+        }
+        if !reachable.contains(&true) {
+            return Some(i);
+        }
+        two_back = one_back;
+        one_back = reachable;
+    }
+    None
+}
+
+// a pattern is only fair if Luigee can follow free lanes through every row
+fn validate(def: &PatternDef) -> Result<SpawnPattern, String> {
+    if def.weight <= 0.0 {
+        return Err("weight must be positive".to_string());
+    }
+    if def.rows.is_empty() {
+        return Err("no rows".to_string());
+    }
+
+    let rows = def
+        .rows
+        .iter()
+        .map(|row| parse_row(row))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(i) = first_unreachable_row(&rows) {
+        return Err(format!("row {} can't be reached from a free lane", i + 1));
+    }
+
+    Ok(SpawnPattern {
+        name: def.name.clone(),
+        weight: def.weight,
+        min_distance: def.min_distance,
+        rows,
+    })
+}
+
+// built once the data assets are in and rebuilt whenever the pattern file is edited
+pub(super) fn build_pattern_table(
+    mut events: MessageReader<AssetEvent<SpawnPatterns>>,
+    data: Option<Res<DataAssets>>,
+    assets: Res<Assets<SpawnPatterns>>,
+    mut table: ResMut<PatternTable>,
+) {
+    let Some(data) = data else {
+        return;
+    };
+    let id = data.patterns.id();
+    let modified = events.read().fold(false, |modified, event| {
+        modified || event.is_loaded_with_dependencies(id) || event.is_modified(id)
+    });
+    if !data.is_added() && !modified {
+        return;
+    }
+    let Some(patterns) = assets.get(id) else {
+        return;
+    };
+
+    table.0.clear();
+    for def in &patterns.patterns {
+        match validate(def) {
+            Ok(pattern) => table.0.push(pattern),
+            Err(reason) => warn!("Rejected spawn pattern \"{}\": {}", def.name, reason),
+        }
+    }
+    info!("Loaded {} spawn patterns", table.0.len());
+}
@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::ActionState;
+
+use crate::{
+    GameState, Resolution, RunState,
+    actions::GameAction,
+    loading::FontAssets,
+    player::Player,
+    save::SaveBackend,
+    settings::{Settings, VolumeChannel},
+    ui::{ButtonColors, ChangeRunState, ChangeState, UiColor},
+};
+
+use super::AbandonedRun;
+
+const VOLUME_STEP: f32 = 0.1;
+
+#[derive(Component)]
+pub(super) struct VolumeButton {
+    channel: VolumeChannel,
+    step: f32,
+}
+
+#[derive(Component)]
+pub(super) struct VolumeText(VolumeChannel);
+
+// the pause menu's quit button, a run left this way isn't banked or recorded
+#[derive(Component)]
+pub(super) struct QuitRun;
+
+// pause toggles the run, backing out of the pause settings returns to the pause menu
+pub(super) fn toggle_pause(
+    run_state: Res<State<RunState>>,
+    mut next_state: ResMut<NextState<RunState>>,
+    action_state: Single<&ActionState<GameAction>, With<Player>>,
+) {
+    if !action_state.just_pressed(&GameAction::Pause) {
+        return;
+    }
+
+    next_state.set(match run_state.get() {
+        RunState::Running => RunState::Paused,
+        RunState::Paused => RunState::Running,
+        RunState::Settings => RunState::Paused,
+    });
+}
+
+fn spawn_overlay<'a>(
+    commands: &'a mut Commands,
+    resolution: &Resolution,
+    state: RunState,
+) -> EntityCommands<'a> {
+    let s = resolution.ui_scale();
+    commands.spawn((
+        Name::new("Pause Overlay"),
+        DespawnOnExit(state),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(16.0 * s),
+            ..default()
+        },
+        BackgroundColor(UiColor::Darkest.color().with_alpha(0.7)),
+        GlobalZIndex(20),
+    ))
+}
+
+fn spawn_button(
+    parent: &mut ChildSpawnerCommands,
+    fonts: &FontAssets,
+    s: f32,
+    label: &str,
+    action: impl Bundle,
+) {
+    let button_colors = ButtonColors::default();
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(200.0 * s),
+                border: UiRect::all(Val::Px(4.0 * s)),
+                padding: UiRect::axes(Val::Px(16.0 * s), Val::Px(8.0 * s)),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(button_colors.normal),
+            BorderColor::all(UiColor::Darkest.color()),
+            button_colors,
+            action,
+        ))
+        .with_child((
+            Text::new(label),
+            TextFont {
+                font: fonts.tiny5.clone(),
+                font_size: 28.0 * s,
+                ..default()
+            },
+            TextColor(UiColor::Darkest.color()),
+        ));
+}
+
+fn spawn_title(parent: &mut ChildSpawnerCommands, fonts: &FontAssets, s: f32, title: &str) {
+    parent.spawn((
+        Text::new(title),
+        TextFont {
+            font: fonts.tiny5.clone(),
+            font_size: 48.0 * s,
+            ..default()
+        },
+        TextColor(UiColor::Lightest.color()),
+    ));
+}
+
+pub(super) fn spawn_pause_menu(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    fonts: Res<FontAssets>,
+) {
+    let s = resolution.ui_scale();
+    spawn_overlay(&mut commands, &resolution, RunState::Paused).with_children(|parent| {
+        spawn_title(parent, &fonts, s, "Paused");
+        spawn_button(
+            parent,
+            &fonts,
+            s,
+            "Resume",
+            ChangeRunState(RunState::Running),
+        );
+        spawn_button(
+            parent,
+            &fonts,
+            s,
+            "Settings",
+            ChangeRunState(RunState::Settings),
+        );
+        spawn_button(
+            parent,
+            &fonts,
+            s,
+            "Quit to Menu",
+            (ChangeState(GameState::Menu), QuitRun),
+        );
+    });
+}
+
+fn volume_label(settings: &Settings, channel: VolumeChannel) -> String {
+    format!(
+        "{}: {:.0}%",
+        channel.label(),
+        settings.volume.get(channel) * 100.0
+    )
+}
+
+// resolution stays on the main settings screen, the lanes can't be rescaled mid-run
+pub(super) fn spawn_pause_settings(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    fonts: Res<FontAssets>,
+    settings: Res<Settings>,
+) {
+    let s = resolution.ui_scale();
+    spawn_overlay(&mut commands, &resolution, RunState::Settings).with_children(|parent| {
+        spawn_title(parent, &fonts, s, "Settings");
+        for channel in VolumeChannel::ALL {
+            parent
+                .spawn(Node {
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(16.0 * s),
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_small_button(row, &fonts, s, "-", channel, -VOLUME_STEP);
+                    row.spawn((
+                        VolumeText(channel),
+                        Text::new(volume_label(&settings, channel)),
+                        Node {
+                            width: Val::Px(200.0 * s),
+                            ..default()
+                        },
+                        TextFont {
+                            font: fonts.tiny5.clone(),
+                            font_size: 28.0 * s,
+                            ..default()
+                        },
+                        TextColor(UiColor::Lightest.color()),
+                        TextLayout::new_with_justify(Justify::Center),
+                    ));
+                    spawn_small_button(row, &fonts, s, "+", channel, VOLUME_STEP);
+                });
+        }
+        spawn_button(parent, &fonts, s, "Back", ChangeRunState(RunState::Paused));
+    });
+}
+
+fn spawn_small_button(
+    parent: &mut ChildSpawnerCommands,
+    fonts: &FontAssets,
+    s: f32,
+    label: &str,
+    channel: VolumeChannel,
+    step: f32,
+) {
+    let button_colors = ButtonColors::default();
+    parent
+        .spawn((
+            Button,
+            VolumeButton { channel, step },
+            Node {
+                border: UiRect::all(Val::Px(2.0 * s)),
+                padding: UiRect::axes(Val::Px(12.0 * s), Val::Px(4.0 * s)),
+                ..default()
+            },
+            BackgroundColor(button_colors.normal),
+            BorderColor::all(UiColor::Darkest.color()),
+            button_colors,
+        ))
+        .with_child((
+            Text::new(label),
+            TextFont {
+                font: fonts.tiny5.clone(),
+                font_size: 28.0 * s,
+                ..default()
+            },
+            TextColor(UiColor::Darkest.color()),
+        ));
+}
+
+// marks the run before the state change lands, so the end of run systems can skip it
+pub(super) fn abandon_run(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<QuitRun>)>,
+) {
+    if interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        commands.insert_resource(AbandonedRun);
+        info!("Run abandoned");
+    }
+}
+
+pub(super) fn adjust_volume(
+    backend: Res<SaveBackend>,
+    mut settings: ResMut<Settings>,
+    interaction_query: Query<(&Interaction, &VolumeButton), Changed<Interaction>>,
+    mut texts: Query<(&mut Text, &VolumeText)>,
+) {
+    let mut changed = false;
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let volume = settings.volume.get_mut(button.channel);
+            *volume = (*volume + button.step).clamp(0.0, 1.0);
+            changed = true;
+        }
+    }
+
+    if changed {
+        for (mut text, volume_text) in &mut texts {
+            **text = volume_label(&settings, volume_text.0);
+        }
+        settings.store(&backend);
+    }
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{LugeState, Resolution};
+
+use super::replay::LugeInput;
+use super::rng::RunRng;
+use super::{PIXELS_PER_METER, RunDistance, RunStats, finish_run};
+
+// all in meters and seconds, the sled has unit mass
+const GRAVITY: f32 = 9.8;
+const SNOW_FRICTION: f32 = 0.03;
+const AIR_DRAG: f32 = 0.0015;
+// braking digs the runners in, multiplying snow friction
+const BRAKE_FRICTION: f32 = 4.0;
+const LAUNCH_SPEED: f32 = 15.0;
+const LAUNCH_SPEED_PER_POINT: f32 = 1.5;
+// the track is cut into segments of random grade, downhill is positive
+const SEGMENT_LENGTH: f32 = 50.0;
+const MIN_SLOPE_DEG: f32 = -4.0;
+const MAX_SLOPE_DEG: f32 = 2.0;
+
+// the sled's velocity down the track in meters per second
+#[derive(Resource, Deref, DerefMut, Copy, Clone, Default)]
+pub struct ScrollSpeed(pub f32);
+
+impl ScrollSpeed {
+    pub fn pixels_per_sec(&self, resolution: &Resolution) -> f32 {
+        self.0 * PIXELS_PER_METER * resolution.scale()
+    }
+
+    // an instant change in velocity, boosts are positive and crashes negative
+    pub fn boost(&mut self, delta: f32) {
+        self.0 = (self.0 + delta).max(0.0);
+    }
+}
+
+// grade of the current track segment
+#[derive(Resource, Default)]
+pub(super) struct Slope {
+    // radians, positive is downhill
+    pub angle: f32,
+    pub segment_end: f32,
+}
+
+// the speed stat is a better sled, launching faster and gliding further
+fn glide(run_stats: &RunStats) -> f32 {
+    1.0 + run_stats.speed as f32 * 0.1
+}
+
+pub(super) fn launch_sled(
+    run_stats: Res<RunStats>,
+    mut scroll_speed: ResMut<ScrollSpeed>,
+    mut slope: ResMut<Slope>,
+) {
+    **scroll_speed = LAUNCH_SPEED + LAUNCH_SPEED_PER_POINT * run_stats.speed as f32;
+    // the launch ramp is flat
+    *slope = Slope {
+        angle: 0.0,
+        segment_end: SEGMENT_LENGTH,
+    };
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn integrate_sled(
+    mut commands: Commands,
+    time: Res<Time>,
+    run_stats: Res<RunStats>,
+    input: Res<LugeInput>,
+    distance: Res<RunDistance>,
+    mut rng: ResMut<RunRng>,
+    mut slope: ResMut<Slope>,
+    mut scroll_speed: ResMut<ScrollSpeed>,
+    mut next_state: ResMut<NextState<LugeState>>,
+) {
+    if **distance >= slope.segment_end {
+        slope.angle = rng.random_range(MIN_SLOPE_DEG..MAX_SLOPE_DEG).to_radians();
+        slope.segment_end += SEGMENT_LENGTH;
+    }
+
+    let glide = glide(&run_stats);
+    let brake = if input.brake { BRAKE_FRICTION } else { 1.0 };
+    let v = **scroll_speed;
+
+    let gravity = GRAVITY * slope.angle.sin();
+    let friction = SNOW_FRICTION / glide * brake * GRAVITY * slope.angle.cos();
+    let drag = AIR_DRAG / glide * v * v;
+    let acceleration = gravity - friction - drag;
+
+    scroll_speed.boost(acceleration * time.delta_secs());
+
+    // friction only slows a moving sled, a stop on anything short of a steep hill ends the run
+    if **scroll_speed == 0.0 {
+        finish_run(&mut commands, &mut next_state);
+    }
+}
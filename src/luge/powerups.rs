@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+
+use crate::{Resolution, ui::UiColor};
+
+use super::spawner::{Coin, LaneOccupant, touches};
+use super::{LuigeeSprite, PlayerLane};
+
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub(super) enum PowerUp {
+    Magnet,
+    Shield,
+    Doubler,
+}
+
+impl PowerUp {
+    pub const ALL: [Self; 3] = [Self::Magnet, Self::Shield, Self::Doubler];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerUp::Magnet => "Magnet",
+            PowerUp::Shield => "Shield",
+            PowerUp::Doubler => "Doubler",
+        }
+    }
+
+    // seconds it lasts once grabbed, the shield also ends early when it takes a hit
+    fn duration(&self) -> f32 {
+        match self {
+            PowerUp::Magnet => 8.0,
+            PowerUp::Shield => 10.0,
+            PowerUp::Doubler => 8.0,
+        }
+    }
+
+    // no art for these yet, a colored square per kind
+    pub fn sprite(&self) -> Sprite {
+        let color = match self {
+            PowerUp::Magnet => UiColor::Dark,
+            PowerUp::Shield => UiColor::Darker,
+            PowerUp::Doubler => UiColor::Lightest,
+        };
+        Sprite::from_color(color.color(), Vec2::splat(10.0))
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+// time left on each power-up, grabbing one again restarts its timer
+#[derive(Resource, Default)]
+pub(super) struct ActivePowerUps([Option<Timer>; 3]);
+
+impl ActivePowerUps {
+    fn activate(&mut self, power_up: PowerUp) {
+        self.0[power_up.index()] = Some(Timer::from_seconds(power_up.duration(), TimerMode::Once));
+    }
+
+    pub fn is_active(&self, power_up: PowerUp) -> bool {
+        self.0[power_up.index()].is_some()
+    }
+
+    pub fn remaining(&self, power_up: PowerUp) -> Option<f32> {
+        self.0[power_up.index()]
+            .as_ref()
+            .map(|timer| timer.remaining_secs())
+    }
+
+    // ends a power-up early, returning whether it was active
+    pub fn consume(&mut self, power_up: PowerUp) -> bool {
+        self.0[power_up.index()].take().is_some()
+    }
+
+    pub fn coin_factor(&self) -> u32 {
+        if self.is_active(PowerUp::Doubler) {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+pub(super) fn tick_power_ups(time: Res<Time>, mut active: ResMut<ActivePowerUps>) {
+    for power_up in PowerUp::ALL {
+        // only borrow mutably for running timers, so the HUD isn't refreshed every tick for nothing
+        if !active.is_active(power_up) {
+            continue;
+        }
+        let slot = &mut active.0[power_up.index()];
+        if let Some(timer) = slot {
+            timer.tick(time.delta());
+            if timer.is_finished() {
+                *slot = None;
+                info!("{} wore off", power_up.label());
+            }
+        }
+    }
+}
+
+pub(super) fn collect_power_ups(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    mut active: ResMut<ActivePowerUps>,
+    power_ups: Query<(Entity, &PowerUp, &Transform)>,
+    luigee: Single<&Transform, (With<LuigeeSprite>, Without<LaneOccupant>)>,
+) {
+    for (entity, power_up, transform) in power_ups.iter() {
+        if touches(&resolution, &luigee, transform) {
+            active.activate(*power_up);
+            commands.entity(entity).despawn();
+            info!("Picked up {}", power_up.label());
+        }
+    }
+}
+
+// world pixels ahead of Luigee the magnet reaches and how fast it drags coins across,
+// before resolution scaling
+const MAGNET_RANGE: f32 = 160.0;
+const MAGNET_PULL: f32 = 240.0;
+
+// drags coins in the lanes next to Luigee's across, collect_coins picks them up once they line up
+pub(super) fn magnet_coins(
+    time: Res<Time>,
+    resolution: Res<Resolution>,
+    active: Res<ActivePowerUps>,
+    player_lane: Res<PlayerLane>,
+    mut coins: Query<(&LaneOccupant, &mut Transform), With<Coin>>,
+    luigee: Single<&Transform, (With<LuigeeSprite>, Without<LaneOccupant>)>,
+) {
+    if !active.is_active(PowerUp::Magnet) {
+        return;
+    }
+
+    let scale = resolution.scale();
+    let lane = **player_lane;
+    for (occupant, mut transform) in &mut coins {
+        let adjacent = occupant.lane != lane
+            && (occupant.lane == lane.shift_left() || occupant.lane == lane.shift_right());
+        let ahead = transform.translation.y - luigee.translation.y;
+        if !adjacent || !(0.0..MAGNET_RANGE * scale).contains(&ahead) {
+            continue;
+        }
+
+        let offset = luigee.translation.x - transform.translation.x;
+        let step = MAGNET_PULL * scale * time.delta_secs();
+        transform.translation.x += offset.clamp(-step, step);
+    }
+}
+
+pub(super) fn reset_power_ups(mut active: ResMut<ActivePowerUps>) {
+    *active = ActivePowerUps::default();
+}
@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::ActionState;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    LugeState, Resolution,
+    actions::GameAction,
+    player::{Player, PlayerStats},
+    save::SaveBackend,
+};
+
+use super::rng::RunSeed;
+use super::spawner::PlayerCoins;
+use super::{RunStats, RunTimer};
+
+const REPLAY_VERSION: u32 = 1;
+const REPLAY_KEY: &str = "replay.ron";
+pub(super) const TIMESTEP_HZ: f64 = 60.0;
+
+// what the luge systems act on during one fixed tick, live or replayed
+#[derive(Resource, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub(super) struct LugeInput {
+    pub left: bool,
+    pub right: bool,
+    pub brake: bool,
+    pub jump: bool,
+    pub duck: bool,
+}
+
+// everything needed to play a run back tick for tick, plus its result to check against
+#[derive(Serialize, Deserialize)]
+pub(super) struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub timestep_hz: f64,
+    pub resolution: Resolution,
+    pub stats: PlayerStats,
+    pub frames: Vec<LugeInput>,
+    pub coins: u32,
+    pub time_secs: f32,
+}
+
+#[derive(Resource, Default)]
+pub(super) struct ReplayRecording(Vec<LugeInput>);
+
+#[derive(Resource)]
+pub(super) struct ReplayPlayback {
+    pub replay: Replay,
+    cursor: usize,
+}
+
+#[derive(Component)]
+pub(super) struct StartReplay;
+
+pub(super) fn sample_input(
+    mut input: ResMut<LugeInput>,
+    playback: Option<ResMut<ReplayPlayback>>,
+    recording: Option<ResMut<ReplayRecording>>,
+    action_state: Single<&ActionState<GameAction>, With<Player>>,
+) {
+    if let Some(mut playback) = playback {
+        let cursor = playback.cursor;
+        *input = playback
+            .replay
+            .frames
+            .get(cursor)
+            .copied()
+            .unwrap_or_default();
+        playback.cursor += 1;
+        return;
+    }
+
+    *input = LugeInput {
+        left: action_state.just_pressed(&GameAction::Left),
+        right: action_state.just_pressed(&GameAction::Right),
+        brake: action_state.pressed(&GameAction::Brake),
+        jump: action_state.just_pressed(&GameAction::Jump),
+        duck: action_state.just_pressed(&GameAction::Duck),
+    };
+
+    if let Some(mut recording) = recording {
+        recording.0.push(*input);
+    }
+}
+
+pub(super) fn start_recording(
+    mut commands: Commands,
+    playback: Option<Res<ReplayPlayback>>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+) {
+    match playback {
+        Some(playback) => fixed_time.set_timestep_hz(playback.replay.timestep_hz),
+        None => {
+            fixed_time.set_timestep_hz(TIMESTEP_HZ);
+            commands.insert_resource(ReplayRecording::default());
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn finish_replay(
+    mut commands: Commands,
+    backend: Res<SaveBackend>,
+    resolution: Res<Resolution>,
+    run_seed: Res<RunSeed>,
+    run_stats: Res<RunStats>,
+    player_coins: Res<PlayerCoins>,
+    timer: Res<RunTimer>,
+    recording: Option<ResMut<ReplayRecording>>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    let time_secs = timer.elapsed_secs();
+
+    if let Some(playback) = playback {
+        let replay = &playback.replay;
+        if replay.coins == **player_coins && replay.time_secs == time_secs {
+            info!("Replay reproduced {} coins in {}s", replay.coins, time_secs);
+        } else {
+            warn!(
+                "Replay diverged: recorded {} coins in {}s, got {} coins in {}s",
+                replay.coins, replay.time_secs, **player_coins, time_secs
+            );
+        }
+        commands.remove_resource::<ReplayPlayback>();
+        return;
+    }
+
+    let Some(mut recording) = recording else {
+        return;
+    };
+
+    let replay = Replay {
+        version: REPLAY_VERSION,
+        seed: run_seed.current,
+        timestep_hz: TIMESTEP_HZ,
+        resolution: *resolution,
+        stats: run_stats.0.clone(),
+        frames: std::mem::take(&mut recording.0),
+        coins: **player_coins,
+        time_secs,
+    };
+    backend.store(REPLAY_KEY, &replay);
+    commands.remove_resource::<ReplayRecording>();
+}
+
+pub(super) fn start_replay(
+    mut commands: Commands,
+    backend: Res<SaveBackend>,
+    resolution: Res<Resolution>,
+    mut next_state: ResMut<NextState<LugeState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<StartReplay>)>,
+) {
+    if !interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        return;
+    }
+
+    let Some(replay) = backend.load::<Replay>(REPLAY_KEY) else {
+        return;
+    };
+
+    if replay.version > REPLAY_VERSION {
+        warn!(
+            "Replay version {} is newer than supported version {}, ignoring it",
+            replay.version, REPLAY_VERSION
+        );
+        return;
+    }
+
+    // lane offsets and hit distances scale with the resolution
+    if replay.resolution.label() != resolution.label() {
+        warn!(
+            "Replay was recorded at {}, playing back at {} may diverge",
+            replay.resolution.label(),
+            resolution.label()
+        );
+    }
+
+    info!(
+        "Playing back replay with seed {} over {} ticks",
+        replay.seed,
+        replay.frames.len()
+    );
+    commands.insert_resource(ReplayPlayback { replay, cursor: 0 });
+    next_state.set(LugeState::Launched);
+}
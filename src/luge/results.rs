@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::ActionState;
+
+use crate::{
+    LugeState, Resolution, actions::GameAction, leaderboard::format_time, loading::FontAssets,
+    player::Player, ui::UiColor,
+};
+
+use super::rng::RunSeed;
+use super::score::RunScore;
+use super::spawner::PlayerCoins;
+use super::{RunDistance, RunTimer};
+
+// coin values in coin atlas order
+pub(super) const COIN_VALUES: [u32; 3] = [1, 5, 25];
+
+// per-run breakdown shown on the results screen
+#[derive(Resource, Default)]
+pub(super) struct RunSummary {
+    pub coins_by_value: [u32; 3],
+    pub smash_coins: u32,
+    // meters per second
+    pub max_speed: f32,
+    pub new_record: bool,
+}
+
+impl RunSummary {
+    pub fn add_coin(&mut self, value: u32) {
+        if let Some(i) = COIN_VALUES.iter().position(|v| *v == value) {
+            self.coins_by_value[i] += 1;
+        }
+    }
+}
+
+pub(super) fn reset_run_summary(mut summary: ResMut<RunSummary>) {
+    *summary = RunSummary::default();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn spawn_results(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    fonts: Res<FontAssets>,
+    summary: Res<RunSummary>,
+    timer: Res<RunTimer>,
+    distance: Res<RunDistance>,
+    score: Res<RunScore>,
+    player_coins: Res<PlayerCoins>,
+    run_seed: Res<RunSeed>,
+) {
+    let s = resolution.ui_scale();
+    let border = 8.0 * s;
+    let font_size = 28.0 * s;
+
+    let mut lines = vec![
+        format!("Time: {}", format_time(timer.elapsed_secs())),
+        format!("Distance: {:.0}m", **distance),
+        format!("Max speed: {:.1}m/s", summary.max_speed),
+    ];
+    for (value, count) in COIN_VALUES.iter().zip(summary.coins_by_value) {
+        lines.push(format!("{}c coins: {} x{}", value, count, value));
+    }
+    lines.push(format!("Smashed: {}c", summary.smash_coins));
+    lines.push(format!("Coins: {}", **player_coins));
+    lines.push(format!("Score: {}", **score));
+    lines.push(format!("Seed: {}", run_seed.current));
+
+    commands
+        .spawn((
+            Name::new("Results Panel"),
+            DespawnOnExit(LugeState::Results),
+            Node {
+                width: Val::Percent(50.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                left: Val::Percent(25.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(border),
+                ..default()
+            },
+            BackgroundColor(UiColor::Darkest.color().with_alpha(0.8)),
+        ))
+        .with_children(|parent| {
+            let font = fonts.tiny5.clone();
+            if summary.new_record {
+                parent.spawn((
+                    Text::new("NEW RECORD!"),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 48.0 * s,
+                        ..default()
+                    },
+                    TextColor(UiColor::Lighter.color()),
+                ));
+            }
+            parent.spawn((
+                Text::new("Run Results"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 40.0 * s,
+                    ..default()
+                },
+                TextColor(UiColor::Lightest.color()),
+            ));
+            for line in lines {
+                parent.spawn((
+                    Text::new(line),
+                    TextFont {
+                        font: font.clone(),
+                        font_size,
+                        ..default()
+                    },
+                    TextColor(UiColor::Lightest.color()),
+                ));
+            }
+            parent.spawn((
+                Text::new("[Space / Click] to continue"),
+                TextFont {
+                    font,
+                    font_size: 16.0 * s,
+                    ..default()
+                },
+                TextColor(UiColor::Lighter.color()),
+            ));
+        });
+}
+
+pub(super) fn continue_from_results(
+    mut next_state: ResMut<NextState<LugeState>>,
+    action_state: Single<&ActionState<GameAction>, With<Player>>,
+) {
+    if action_state.just_pressed(&GameAction::Continue) {
+        next_state.set(LugeState::Loadout);
+    }
+}
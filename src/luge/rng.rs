@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use super::daily::DailyChallenge;
+use super::replay::ReplayPlayback;
+
+// the only source of gameplay randomness, reseeded at every launch
+#[derive(Resource, Deref, DerefMut)]
+pub(super) struct RunRng(StdRng);
+
+impl Default for RunRng {
+    fn default() -> Self {
+        Self(StdRng::seed_from_u64(0))
+    }
+}
+
+#[derive(Resource, Default)]
+pub(super) struct RunSeed {
+    // seed of the run in progress, or of the last finished run
+    pub current: u64,
+    // seed picked in the loadout, a random one is rolled when unset
+    pub requested: Option<u64>,
+}
+
+pub(super) fn seed_run_rng(
+    mut run_rng: ResMut<RunRng>,
+    mut run_seed: ResMut<RunSeed>,
+    playback: Option<Res<ReplayPlayback>>,
+    daily: Option<Res<DailyChallenge>>,
+) {
+    let seed = if let Some(playback) = playback {
+        playback.replay.seed
+    } else if let Some(daily) = daily {
+        daily.seed()
+    } else {
+        run_seed
+            .requested
+            .unwrap_or_else(|| rand::rng().random::<u64>())
+    };
+    run_seed.current = seed;
+    **run_rng = StdRng::seed_from_u64(seed);
+    info!("Launched with seed {}", seed);
+}
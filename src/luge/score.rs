@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+use crate::{
+    date::Date,
+    leaderboard::{LastRunRank, Leaderboard, RunRecord},
+    save::SaveBackend,
+};
+
+use super::results::RunSummary;
+use super::spawner::PlayerCoins;
+use super::{RunDistance, RunStats, RunTimer};
+
+// points per meter slid, per coin banked and per second survived
+const DISTANCE_POINTS: f32 = 10.0;
+const COIN_POINTS: f32 = 25.0;
+const TIME_POINTS: f32 = 5.0;
+
+#[derive(Resource, Default, Deref, DerefMut, Copy, Clone)]
+pub(crate) struct RunScore(pub u32);
+
+pub(crate) fn run_score(distance: f32, coins: u32, elapsed_secs: f32) -> u32 {
+    (distance * DISTANCE_POINTS + coins as f32 * COIN_POINTS + elapsed_secs * TIME_POINTS) as u32
+}
+
+pub(super) fn update_run_score(
+    distance: Res<RunDistance>,
+    player_coins: Res<PlayerCoins>,
+    timer: Res<RunTimer>,
+    mut score: ResMut<RunScore>,
+) {
+    let new_score = run_score(**distance, **player_coins, timer.elapsed_secs());
+    if **score != new_score {
+        **score = new_score;
+    }
+}
+
+pub(super) fn reset_run_score(mut distance: ResMut<RunDistance>, mut score: ResMut<RunScore>) {
+    **distance = 0.0;
+    **score = 0;
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn record_run(
+    backend: Res<SaveBackend>,
+    score: Res<RunScore>,
+    distance: Res<RunDistance>,
+    player_coins: Res<PlayerCoins>,
+    timer: Res<RunTimer>,
+    run_stats: Res<RunStats>,
+    mut leaderboard: ResMut<Leaderboard>,
+    mut last_run_rank: ResMut<LastRunRank>,
+    mut summary: ResMut<RunSummary>,
+) {
+    **last_run_rank = leaderboard.submit(RunRecord {
+        score: **score,
+        coins: **player_coins,
+        time_secs: timer.elapsed_secs(),
+        distance: **distance,
+        stats: run_stats.0.clone(),
+        date: Date::today(),
+    });
+
+    summary.new_record = **last_run_rank == Some(0);
+    if let Some(rank) = **last_run_rank {
+        info!("Run placed #{} with {} points", rank + 1, **score);
+        leaderboard.store(&backend);
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use crate::{
+    player::{PlayerStats, StatKind, Wallet},
+    save::SaveRequested,
+};
+
+use super::dialogue::RickDialogue;
+
+#[derive(Component)]
+pub(super) struct BuyStat(pub StatKind);
+
+pub(super) fn buy_stat(
+    mut commands: Commands,
+    mut player_stats: ResMut<PlayerStats>,
+    mut wallet: ResMut<Wallet>,
+    mut rick_text: Single<&mut Text, With<RickDialogue>>,
+    interaction_query: Query<(&Interaction, &BuyStat), Changed<Interaction>>,
+) {
+    for (interaction, buy) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let price = player_stats.upgrade_price(buy.0);
+        if **wallet < price {
+            rick_text.0 = format!(
+                "Dat {} upgrade costs {} Slick Coins, pal. Come back when ya got da dough.",
+                buy.0.label(),
+                price
+            );
+            continue;
+        }
+
+        **wallet -= price;
+        *player_stats.get_mut(buy.0) += 1;
+        info!(
+            "Bought {} level {} for {}",
+            buy.0.label(),
+            player_stats.get(buy.0),
+            price
+        );
+        rick_text.0 = "Pleasure doin business wit ya. Heh heh.".to_string();
+        commands.insert_resource(SaveRequested);
+    }
+}
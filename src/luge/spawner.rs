@@ -1,9 +1,23 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use rand::Rng;
 
-use crate::{Resolution, loading::SpriteAssets, player::PlayerStats};
+use crate::{
+    Resolution, animation::AtlasAnimation, loading::SpriteAssets, player::Wallet,
+    save::SaveRequested, ui::UiColor,
+};
 
-use super::{LaneLocation, Lanes, LuigeeSprite, PlayerLane, ScrollSpeed};
+use super::difficulty::Difficulty;
+use super::effects::{CoinCollected, SpeedBoosted};
+use super::patterns::PatternTable;
+use super::powerups::{ActivePowerUps, PowerUp};
+use super::results::RunSummary;
+use super::rng::RunRng;
+use super::stance::Stance;
+use super::{
+    Durability, LaneLocation, Lanes, LuigeeSprite, PlayerLane, RunDistance, RunStats, ScrollSpeed,
+};
 
 #[derive(Component)]
 pub(super) struct LaneOccupant {
@@ -15,31 +29,195 @@ pub(super) struct Coin {
     pub value: u32,
 }
 
+// floats above the track, only reachable mid-jump
+#[derive(Component)]
+pub(super) struct AirCoin;
+
+// low ones are jumped over, high ones are ducked under
+#[derive(Component, Clone, Copy, Debug)]
+pub(super) enum Obstacle {
+    Low,
+    High,
+}
+
+impl Obstacle {
+    fn cleared_by(&self, stance: Stance) -> bool {
+        match self {
+            Obstacle::Low => stance.is_airborne(),
+            Obstacle::High => stance.is_crouched(),
+        }
+    }
+
+    // no art for these yet, a log across the lane and a banner above it
+    fn sprite(&self) -> Sprite {
+        match self {
+            Obstacle::Low => Sprite::from_color(UiColor::Darkest.color(), Vec2::new(28.0, 6.0)),
+            Obstacle::High => Sprite::from_color(UiColor::Lighter.color(), Vec2::new(32.0, 4.0)),
+        }
+    }
+}
+
+// set on anything Luigee already crashed into or rode over so it can't trigger again while it scrolls past
+#[derive(Component)]
+pub(super) struct Struck;
+
+// set on coins that scrolled past Luigee without being grabbed, so each breaks the combo only once
+#[derive(Component)]
+pub(super) struct Missed;
+
+// pads are ridden over on the ground and stay on the track, pickups are grabbed in any stance
+#[derive(Component, Clone, Copy, Debug)]
+pub(super) enum Boost {
+    Pad,
+    Pickup,
+}
+
+impl Boost {
+    // meters per second gained
+    fn speed(&self) -> f32 {
+        match self {
+            Boost::Pad => 6.0,
+            Boost::Pickup => 3.0,
+        }
+    }
+
+    fn reached_by(&self, stance: Stance) -> bool {
+        match self {
+            Boost::Pad => !stance.is_airborne(),
+            Boost::Pickup => true,
+        }
+    }
+
+    // no art for these yet, a strip across the lane and a small gem
+    fn sprite(&self) -> Sprite {
+        match self {
+            Boost::Pad => Sprite::from_color(UiColor::Lighter.color(), Vec2::new(24.0, 12.0)),
+            Boost::Pickup => Sprite::from_color(UiColor::Lightest.color(), Vec2::splat(8.0)),
+        }
+    }
+}
+
+#[derive(Component)]
+pub(super) struct Enemy {
+    pub toughness: i32,
+}
+
+impl Enemy {
+    const MAX_TOUGHNESS: i32 = 3;
+
+    // tougher enemies are tinted darker so they can be read at a glance
+    fn tint(&self) -> Color {
+        match self.toughness {
+            1 => Color::WHITE,
+            2 => UiColor::Lighter.color(),
+            _ => UiColor::Light.color(),
+        }
+    }
+}
+
+// coins collected during the current run, banked into the wallet when it ends
 #[derive(Resource, Default, Deref, DerefMut)]
 pub(super) struct PlayerCoins(pub u32);
 
+// frames per coin denomination and per enemy, laid out left to right,
+// a coin spin and an enemy idle bob
+const COIN_FRAMES: usize = 4;
+const ENEMY_FRAMES: usize = 2;
+const COIN_FPS: f32 = 10.0;
+const ENEMY_FPS: f32 = 6.0;
+
+fn coin_sprite(sprites: &SpriteAssets, layout: &CoinAtlasLayout, denomination: usize) -> Sprite {
+    Sprite::from_atlas_image(
+        sprites.coins.clone(),
+        TextureAtlas {
+            layout: layout.0.clone(),
+            index: denomination * COIN_FRAMES,
+        },
+    )
+}
+
+fn coin_animation(denomination: usize) -> AtlasAnimation {
+    AtlasAnimation::looping(denomination * COIN_FRAMES, COIN_FRAMES, COIN_FPS)
+}
+
+// consecutive coins grabbed without missing one or crashing
+#[derive(Resource, Default)]
+pub(super) struct Combo {
+    pub streak: u32,
+}
+
+impl Combo {
+    // coins in a row needed for each step up in multiplier
+    const STEP: u32 = 5;
+    const MAX_MULTIPLIER: u32 = 5;
+
+    pub fn multiplier(&self) -> u32 {
+        (1 + self.streak / Self::STEP).min(Self::MAX_MULTIPLIER)
+    }
+
+    fn reset(&mut self) {
+        if self.streak > 0 {
+            info!("Combo of {} broken", self.streak);
+        }
+        self.streak = 0;
+    }
+}
+
 #[derive(Resource)]
 pub(super) struct CoinAtlasLayout(Handle<TextureAtlasLayout>);
 
+#[derive(Resource)]
+pub(super) struct EnemyAtlasLayout(Handle<TextureAtlasLayout>);
+
 #[derive(Resource, Deref, DerefMut)]
-pub(super) struct SpawnTimer(Timer);
+pub(super) struct SpawnTimer {
+    #[deref]
+    timer: Timer,
+    // pixels the last pattern still sticks out above the spawn line,
+    // the timer holds until it has scrolled down so nothing lands inside it
+    clearance: f32,
+}
 
 pub(super) fn init_coin_atlas(
     mut commands: Commands,
     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(32), 3, 1, None, None);
+    let layout =
+        TextureAtlasLayout::from_grid(UVec2::splat(32), 3 * COIN_FRAMES as u32, 1, None, None);
     let handle = layouts.add(layout);
     commands.insert_resource(CoinAtlasLayout(handle));
 }
 
-pub(super) fn init_spawn_timer(mut commands: Commands, player_stats: Res<PlayerStats>) {
-    let luck = player_stats.luck as f32;
-    let interval = (2.5 / (1.0 + luck * 0.1)).max(0.8);
-    commands.insert_resource(SpawnTimer(Timer::from_seconds(
-        interval,
-        TimerMode::Repeating,
-    )));
+pub(super) fn init_enemy_atlas(
+    mut commands: Commands,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let layout =
+        TextureAtlasLayout::from_grid(UVec2::splat(32), ENEMY_FRAMES as u32, 1, None, None);
+    let handle = layouts.add(layout);
+    commands.insert_resource(EnemyAtlasLayout(handle));
+}
+
+const MIN_SPAWN_INTERVAL: f32 = 0.5;
+
+// luck brings spawns closer together
+fn spawn_interval(difficulty: &Difficulty, run_stats: &RunStats) -> f32 {
+    let luck = run_stats.luck as f32;
+    (difficulty.spawn_interval / (1.0 + luck * 0.1)).max(MIN_SPAWN_INTERVAL)
+}
+
+pub(super) fn init_spawn_timer(
+    mut commands: Commands,
+    difficulty: Res<Difficulty>,
+    run_stats: Res<RunStats>,
+) {
+    commands.insert_resource(SpawnTimer {
+        timer: Timer::from_seconds(
+            spawn_interval(&difficulty, &run_stats),
+            TimerMode::Repeating,
+        ),
+        clearance: 0.0,
+    });
 }
 
 pub(super) fn spawn_initial_coin(
@@ -58,13 +236,8 @@ pub(super) fn spawn_initial_coin(
     let lane_x = lanes.x_for(lane);
 
     commands.spawn((
-        Sprite::from_atlas_image(
-            sprites.coins.clone(),
-            TextureAtlas {
-                layout: coin_atlas.0.clone(),
-                index: 0,
-            },
-        ),
+        coin_sprite(&sprites, &coin_atlas, 0),
+        coin_animation(0),
         Transform {
             translation: Vec3::new(lane_x, y, 0.5),
             scale: Vec3::splat(resolution.scale()),
@@ -75,28 +248,232 @@ pub(super) fn spawn_initial_coin(
     ));
 }
 
+// air coins are drawn bigger, as if closer to the camera
+const AIR_COIN_SCALE: f32 = 1.3;
+
+// what a spawn puts in a lane, coin values and enemy toughness are rolled when it's placed
+#[derive(Clone, Copy, Debug)]
+pub(super) enum OccupantKind {
+    Coin,
+    AirCoin,
+    Enemy,
+    Obstacle(Obstacle),
+    Boost(Boost),
+    PowerUp(PowerUp),
+}
+
+impl OccupantKind {
+    // hazards Luigee can't safely ride through in this stance without the right stats,
+    // enemies can't be jumped or ducked
+    pub fn blocks(&self, stance: Stance) -> bool {
+        match self {
+            OccupantKind::Enemy => true,
+            OccupantKind::Obstacle(obstacle) => !obstacle.cleared_by(stance),
+            _ => false,
+        }
+    }
+}
+
+// everything needed to place an occupant, shared by single spawns and patterns
+struct OccupantPlacer<'a> {
+    sprites: &'a SpriteAssets,
+    coin_atlas: &'a CoinAtlasLayout,
+    enemy_atlas: &'a EnemyAtlasLayout,
+    scale: f32,
+    luck: f32,
+}
+
+impl OccupantPlacer<'_> {
+    fn place(
+        &self,
+        commands: &mut Commands,
+        rng: &mut RunRng,
+        kind: OccupantKind,
+        lane: LaneLocation,
+        position: Vec2,
+    ) {
+        let transform = |z: f32, scale: f32| Transform {
+            translation: position.extend(z),
+            scale: Vec3::splat(scale),
+            ..default()
+        };
+
+        match kind {
+            OccupantKind::Enemy => {
+                let enemy = Enemy {
+                    toughness: rng.random_range(1..=Enemy::MAX_TOUGHNESS),
+                };
+                commands.spawn((
+                    Sprite {
+                        color: enemy.tint(),
+                        ..Sprite::from_atlas_image(
+                            self.sprites.enemies.clone(),
+                            TextureAtlas {
+                                layout: self.enemy_atlas.0.clone(),
+                                index: 0,
+                            },
+                        )
+                    },
+                    AtlasAnimation::looping(0, ENEMY_FRAMES, ENEMY_FPS),
+                    transform(0.5, self.scale),
+                    LaneOccupant { lane },
+                    enemy,
+                ));
+            }
+            OccupantKind::Obstacle(obstacle) => {
+                commands.spawn((
+                    obstacle.sprite(),
+                    transform(0.4, self.scale),
+                    LaneOccupant { lane },
+                    obstacle,
+                ));
+            }
+            OccupantKind::Boost(boost) => {
+                // pads lie flat under everything else in the lane
+                let z = if matches!(boost, Boost::Pad) {
+                    0.3
+                } else {
+                    0.5
+                };
+                commands.spawn((
+                    boost.sprite(),
+                    transform(z, self.scale),
+                    LaneOccupant { lane },
+                    boost,
+                ));
+            }
+            OccupantKind::PowerUp(power_up) => {
+                commands.spawn((
+                    power_up.sprite(),
+                    transform(0.5, self.scale),
+                    LaneOccupant { lane },
+                    power_up,
+                ));
+            }
+            OccupantKind::Coin | OccupantKind::AirCoin => {
+                let (denomination, value) = self.roll_coin(rng);
+                let airborne = matches!(kind, OccupantKind::AirCoin);
+                let transform = if airborne {
+                    transform(1.5, self.scale * AIR_COIN_SCALE)
+                } else {
+                    transform(0.5, self.scale)
+                };
+
+                let mut coin = commands.spawn((
+                    coin_sprite(self.sprites, self.coin_atlas, denomination),
+                    coin_animation(denomination),
+                    transform,
+                    LaneOccupant { lane },
+                    Coin { value },
+                ));
+                if airborne {
+                    coin.insert(AirCoin);
+                }
+            }
+        }
+    }
+
+    // Weighted coin type selection
+    fn roll_coin(&self, rng: &mut RunRng) -> (usize, u32) {
+        let weight_1 = 100.0_f32;
+        let weight_5 = 10.0 + self.luck * 5.0;
+        let weight_25 = 2.0 + self.luck * 2.0;
+        let total = weight_1 + weight_5 + weight_25;
+
+        let roll: f32 = rng.random_range(0.0..total);
+        if roll < weight_1 {
+            (0, 1)
+        } else if roll < weight_1 + weight_5 {
+            (1, 5)
+        } else {
+            (2, 25)
+        }
+    }
+
+    // Weighted power-up selection, most coin spawns stay coins
+    fn roll_power_up(&self, rng: &mut RunRng) -> Option<PowerUp> {
+        let weight_coin = 100.0_f32;
+        let weight_each = 1.0 + self.luck * 0.5;
+        let total = weight_coin + weight_each * PowerUp::ALL.len() as f32;
+
+        let roll: f32 = rng.random_range(0.0..total);
+        if roll < weight_coin {
+            return None;
+        }
+        let index = ((roll - weight_coin) / weight_each) as usize;
+        Some(PowerUp::ALL[index.min(PowerUp::ALL.len() - 1)])
+    }
+}
+
+// world pixels between pattern rows, before resolution scaling
+const PATTERN_ROW_SPACING: f32 = 40.0;
+
 #[allow(clippy::too_many_arguments)]
-pub(super) fn spawn_coins(
+pub(super) fn spawn_occupants(
     mut commands: Commands,
     time: Res<Time>,
     resolution: Res<Resolution>,
     sprites: Res<SpriteAssets>,
-    player_stats: Res<PlayerStats>,
+    run_stats: Res<RunStats>,
+    difficulty: Res<Difficulty>,
+    distance: Res<RunDistance>,
+    patterns: Res<PatternTable>,
     lanes: Res<Lanes>,
+    scroll_speed: Res<ScrollSpeed>,
     coin_atlas: Option<Res<CoinAtlasLayout>>,
+    enemy_atlas: Option<Res<EnemyAtlasLayout>>,
     mut spawn_timer: Option<ResMut<SpawnTimer>>,
+    mut rng: ResMut<RunRng>,
 ) {
-    let (Some(coin_atlas), Some(ref mut spawn_timer)) = (coin_atlas, spawn_timer.as_mut()) else {
+    let (Some(coin_atlas), Some(enemy_atlas), Some(ref mut spawn_timer)) =
+        (coin_atlas, enemy_atlas, spawn_timer.as_mut())
+    else {
         return;
     };
 
+    if spawn_timer.clearance > 0.0 {
+        spawn_timer.clearance -= scroll_speed.pixels_per_sec(&resolution) * time.delta_secs();
+        return;
+    }
+
+    // the curve keeps shortening the interval as the run goes on
+    let interval = Duration::from_secs_f32(spawn_interval(&difficulty, &run_stats));
+    if spawn_timer.duration() != interval {
+        spawn_timer.set_duration(interval);
+    }
     spawn_timer.tick(time.delta());
     if !spawn_timer.just_finished() {
         return;
     }
 
-    let mut rng = rand::rng();
-    let luck = player_stats.luck as f32;
+    let placer = OccupantPlacer {
+        sprites: &sprites,
+        coin_atlas: &coin_atlas,
+        enemy_atlas: &enemy_atlas,
+        scale: resolution.scale(),
+        luck: run_stats.luck as f32,
+    };
+    let y = resolution.vec2().y / 2.0 + 50.0;
+
+    if rng.random_bool(difficulty.pattern_chance.clamp(0.0, 1.0))
+        && let Some(pattern) = patterns.pick(**distance, &mut rng)
+    {
+        let mirrored = rng.random_bool(0.5);
+        let spacing = PATTERN_ROW_SPACING * resolution.scale();
+        for (i, row) in pattern.rows.iter().enumerate() {
+            for (lane, cell) in LaneLocation::ALL.into_iter().zip(row) {
+                let Some(kind) = *cell else {
+                    continue;
+                };
+                let lane = if mirrored { lane.mirrored() } else { lane };
+                let position = Vec2::new(lanes.x_for(lane), y + i as f32 * spacing);
+                placer.place(&mut commands, &mut rng, kind, lane, position);
+            }
+        }
+        spawn_timer.clearance = (pattern.rows.len() - 1) as f32 * spacing;
+        info!("Spawned pattern \"{}\"", pattern.name);
+        return;
+    }
 
     // Pick random lane
     let lane = match rng.random_range(0..3) {
@@ -105,71 +482,228 @@ pub(super) fn spawn_coins(
         _ => LaneLocation::Right,
     };
 
-    // Weighted coin type selection
-    let weight_1 = 100.0_f32;
-    let weight_5 = 10.0 + luck * 5.0;
-    let weight_25 = 2.0 + luck * 2.0;
-    let total = weight_1 + weight_5 + weight_25;
-
-    let roll: f32 = rng.random_range(0.0..total);
-    let (atlas_index, value) = if roll < weight_1 {
-        (0, 1)
-    } else if roll < weight_1 + weight_5 {
-        (1, 5)
+    let roll: f64 = rng.random();
+    let kind = if roll < difficulty.enemy_chance {
+        OccupantKind::Enemy
+    } else if roll < difficulty.enemy_chance + difficulty.obstacle_chance {
+        if rng.random_bool(0.5) {
+            OccupantKind::Obstacle(Obstacle::Low)
+        } else {
+            OccupantKind::Obstacle(Obstacle::High)
+        }
+    } else if rng.random_bool(difficulty.boost_chance.clamp(0.0, 1.0)) {
+        // pickups are the rarer, weaker boost
+        if rng.random_bool(0.3) {
+            OccupantKind::Boost(Boost::Pickup)
+        } else {
+            OccupantKind::Boost(Boost::Pad)
+        }
+    } else if rng.random_bool(difficulty.air_coin_chance.clamp(0.0, 1.0)) {
+        OccupantKind::AirCoin
+    } else if let Some(power_up) = placer.roll_power_up(&mut rng) {
+        OccupantKind::PowerUp(power_up)
     } else {
-        (2, 25)
+        OccupantKind::Coin
     };
 
-    let lane_x = lanes.x_for(lane);
-    let y = resolution.vec2().y / 2.0 + 50.0;
-
-    commands.spawn((
-        Sprite::from_atlas_image(
-            sprites.coins.clone(),
-            TextureAtlas {
-                layout: coin_atlas.0.clone(),
-                index: atlas_index,
-            },
-        ),
-        Transform {
-            translation: Vec3::new(lane_x, y, 0.5),
-            scale: Vec3::splat(resolution.scale()),
-            ..default()
-        },
-        LaneOccupant { lane },
-        Coin { value },
-    ));
+    let position = Vec2::new(lanes.x_for(lane), y);
+    placer.place(&mut commands, &mut rng, kind, lane, position);
 }
 
 pub(super) fn scroll_occupants(
     time: Res<Time>,
-    player_stats: Res<PlayerStats>,
+    resolution: Res<Resolution>,
     scroll_speed: Res<ScrollSpeed>,
     mut query: Query<&mut Transform, With<LaneOccupant>>,
 ) {
-    let speed = **scroll_speed * (player_stats.speed as f32 / 10.0);
-    let delta = speed * time.delta_secs();
+    let delta = scroll_speed.pixels_per_sec(&resolution) * time.delta_secs();
     for mut transform in query.iter_mut() {
         transform.translation.y -= delta;
     }
 }
 
+// how far off a lane's center Luigee can be and still touch what's in it,
+// under half the lane spacing so nothing is hit from between two lanes
+const LANE_REACH: f32 = 24.0;
+// how far above or below Luigee something can be and still be touched
+const TRACK_REACH: f32 = 40.0;
+
+// collisions use where Luigee actually is, not the lane being steered into
+pub(super) fn touches(resolution: &Resolution, luigee: &Transform, occupant: &Transform) -> bool {
+    let scale = resolution.scale();
+    (occupant.translation.x - luigee.translation.x).abs() < LANE_REACH * scale
+        && (occupant.translation.y - luigee.translation.y).abs() < TRACK_REACH * scale
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(super) fn collect_coins(
     mut commands: Commands,
     resolution: Res<Resolution>,
-    player_lane: Res<PlayerLane>,
     mut player_coins: ResMut<PlayerCoins>,
-    coins: Query<(Entity, &LaneOccupant, &Coin, &Transform)>,
+    mut summary: ResMut<RunSummary>,
+    mut combo: ResMut<Combo>,
+    stance: Res<Stance>,
+    power_ups: Res<ActivePowerUps>,
+    mut collected: MessageWriter<CoinCollected>,
+    coins: Query<(Entity, &Coin, &Transform, Has<AirCoin>)>,
     luigee: Single<&Transform, (With<LuigeeSprite>, Without<LaneOccupant>)>,
 ) {
-    let threshold = 40.0 * resolution.scale();
-    let luigee_y = luigee.translation.y;
+    for (entity, coin, transform, in_air) in coins.iter() {
+        // a jump carries Luigee over ground coins and up to the air ones
+        if in_air == stance.is_airborne() && touches(&resolution, &luigee, transform) {
+            let value = coin.value * power_ups.coin_factor() * combo.multiplier();
+            combo.streak += 1;
+            **player_coins += value;
+            summary.add_coin(coin.value);
+            collected.write(CoinCollected {
+                value,
+                position: transform.translation,
+            });
+            commands.entity(entity).despawn();
+        }
+    }
+}
 
-    for (entity, occupant, coin, transform) in coins.iter() {
-        if occupant.lane == **player_lane && (transform.translation.y - luigee_y).abs() < threshold
-        {
-            **player_coins += coin.value;
+// meters per second lost in a crash
+const HIT_SPEED_PENALTY: f32 = 4.0;
+const ENEMY_DAMAGE: i32 = 5;
+// coins dropped per point of toughness when an enemy is smashed
+const SMASH_REWARD: u32 = 5;
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn hit_enemies(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    run_stats: Res<RunStats>,
+    mut player_lane: ResMut<PlayerLane>,
+    mut player_coins: ResMut<PlayerCoins>,
+    mut summary: ResMut<RunSummary>,
+    mut scroll_speed: ResMut<ScrollSpeed>,
+    mut durability: ResMut<Durability>,
+    mut combo: ResMut<Combo>,
+    mut power_ups: ResMut<ActivePowerUps>,
+    mut rng: ResMut<RunRng>,
+    enemies: Query<(Entity, &LaneOccupant, &Enemy, &Transform), Without<Struck>>,
+    luigee: Single<&Transform, (With<LuigeeSprite>, Without<LaneOccupant>)>,
+) {
+    for (entity, occupant, enemy, transform) in enemies.iter() {
+        if !touches(&resolution, &luigee, transform) {
+            continue;
+        }
+
+        if run_stats.attack >= enemy.toughness {
+            // strong enough to smash straight through
+            let reward = enemy.toughness as u32 * SMASH_REWARD;
+            **player_coins += reward;
+            summary.smash_coins += reward;
             commands.entity(entity).despawn();
+            info!("Smashed enemy in {:?}", occupant.lane);
+        } else if power_ups.consume(PowerUp::Shield) {
+            commands.entity(entity).insert(Struck);
+            info!("Shield absorbed enemy in {:?}", occupant.lane);
+        } else {
+            // a crash costs speed and durability, running out of either ends the run
+            scroll_speed.boost(-HIT_SPEED_PENALTY);
+            let taken = durability.take_hit(ENEMY_DAMAGE, run_stats.defence);
+            combo.reset();
+            **player_lane = player_lane.bounce(rng.random_bool(0.5));
+            commands.entity(entity).insert(Struck);
+            info!(
+                "Bounced off enemy in {:?} for {} damage",
+                occupant.lane, taken
+            );
+        }
+        // only one collision per tick, the bounce sends Luigee out of the lane
+        break;
+    }
+}
+
+const OBSTACLE_DAMAGE: i32 = 3;
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn hit_obstacles(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    run_stats: Res<RunStats>,
+    stance: Res<Stance>,
+    mut scroll_speed: ResMut<ScrollSpeed>,
+    mut durability: ResMut<Durability>,
+    mut combo: ResMut<Combo>,
+    mut power_ups: ResMut<ActivePowerUps>,
+    obstacles: Query<(Entity, &Obstacle, &Transform), Without<Struck>>,
+    luigee: Single<&Transform, (With<LuigeeSprite>, Without<LaneOccupant>)>,
+) {
+    for (entity, obstacle, transform) in obstacles.iter() {
+        if obstacle.cleared_by(*stance) || !touches(&resolution, &luigee, transform) {
+            continue;
+        }
+
+        commands.entity(entity).insert(Struck);
+        if power_ups.consume(PowerUp::Shield) {
+            info!("Shield absorbed {:?} obstacle", obstacle);
+            break;
+        }
+
+        scroll_speed.boost(-HIT_SPEED_PENALTY);
+        let taken = durability.take_hit(OBSTACLE_DAMAGE, run_stats.defence);
+        combo.reset();
+        info!("Crashed into {:?} obstacle for {} damage", obstacle, taken);
+        break;
+    }
+}
+
+// boosts only count in the lane Luigee is steering into, so cutting across one mid-shift misses it
+#[allow(clippy::too_many_arguments)]
+pub(super) fn ride_boosts(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    player_lane: Res<PlayerLane>,
+    stance: Res<Stance>,
+    mut scroll_speed: ResMut<ScrollSpeed>,
+    mut boosted: MessageWriter<SpeedBoosted>,
+    boosts: Query<(Entity, &LaneOccupant, &Boost, &Transform), Without<Struck>>,
+    luigee: Single<&Transform, (With<LuigeeSprite>, Without<LaneOccupant>)>,
+) {
+    for (entity, occupant, boost, transform) in boosts.iter() {
+        if occupant.lane != **player_lane
+            || !boost.reached_by(*stance)
+            || !touches(&resolution, &luigee, transform)
+        {
+            continue;
+        }
+
+        scroll_speed.boost(boost.speed());
+        boosted.write(SpeedBoosted {
+            speed: boost.speed(),
+        });
+        match boost {
+            Boost::Pad => {
+                commands.entity(entity).insert(Struck);
+            }
+            Boost::Pickup => {
+                commands.entity(entity).despawn();
+            }
+        }
+        info!(
+            "Hit {:?} boost in {:?}, now {:.1} m/s",
+            boost, occupant.lane, **scroll_speed
+        );
+    }
+}
+
+// collected coins are despawned on pickup, any coin that drops out of reach below Luigee was missed
+pub(super) fn miss_coins(
+    mut commands: Commands,
+    resolution: Res<Resolution>,
+    mut combo: ResMut<Combo>,
+    coins: Query<(Entity, &Transform), (With<Coin>, Without<Missed>)>,
+    luigee: Single<&Transform, (With<LuigeeSprite>, Without<LaneOccupant>)>,
+) {
+    let cutoff = luigee.translation.y - TRACK_REACH * resolution.scale();
+    for (entity, transform) in coins.iter() {
+        if transform.translation.y <= cutoff {
+            combo.reset();
+            commands.entity(entity).insert(Missed);
         }
     }
 }
@@ -194,6 +728,17 @@ pub(super) fn cleanup_spawner(mut commands: Commands, query: Query<Entity, With<
     commands.remove_resource::<SpawnTimer>();
 }
 
-pub(super) fn reset_player_coins(mut player_coins: ResMut<PlayerCoins>) {
+pub(super) fn reset_player_coins(mut player_coins: ResMut<PlayerCoins>, mut combo: ResMut<Combo>) {
     **player_coins = 0;
+    *combo = Combo::default();
+}
+
+pub(super) fn deposit_player_coins(
+    mut commands: Commands,
+    player_coins: Res<PlayerCoins>,
+    mut wallet: ResMut<Wallet>,
+) {
+    **wallet += **player_coins;
+    commands.insert_resource(SaveRequested);
+    info!("Banked {} coins, wallet holds {}", **player_coins, **wallet);
 }
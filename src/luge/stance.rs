@@ -0,0 +1,71 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::Resolution;
+
+use super::LuigeeSprite;
+use super::replay::LugeInput;
+
+const JUMP_SECS: f32 = 0.6;
+const DUCK_SECS: f32 = 0.6;
+// how much bigger Luigee looks at the top of a jump
+const JUMP_SCALE: f32 = 0.3;
+
+// Luigee's vertical state, timed states hold the seconds spent in them
+#[derive(Resource, Default, Clone, Copy, PartialEq, Debug)]
+pub(super) enum Stance {
+    #[default]
+    Riding,
+    Airborne(f32),
+    Crouched(f32),
+}
+
+impl Stance {
+    pub fn is_airborne(&self) -> bool {
+        matches!(self, Stance::Airborne(_))
+    }
+
+    pub fn is_crouched(&self) -> bool {
+        matches!(self, Stance::Crouched(_))
+    }
+}
+
+// a jump or duck has to finish before the next one can start
+pub(super) fn update_stance(time: Res<Time>, input: Res<LugeInput>, mut stance: ResMut<Stance>) {
+    let delta = time.delta_secs();
+    let next = match *stance {
+        Stance::Riding if input.jump => Stance::Airborne(0.0),
+        Stance::Riding if input.duck => Stance::Crouched(0.0),
+        Stance::Riding => Stance::Riding,
+        Stance::Airborne(t) if t + delta >= JUMP_SECS => Stance::Riding,
+        Stance::Airborne(t) => Stance::Airborne(t + delta),
+        Stance::Crouched(t) if t + delta >= DUCK_SECS => Stance::Riding,
+        Stance::Crouched(t) => Stance::Crouched(t + delta),
+    };
+    if *stance != next {
+        *stance = next;
+    }
+}
+
+pub(super) fn show_stance(
+    resolution: Res<Resolution>,
+    stance: Res<Stance>,
+    mut transform: Single<&mut Transform, With<LuigeeSprite>>,
+) {
+    let factor = match *stance {
+        Stance::Riding => Vec3::ONE,
+        Stance::Airborne(t) => Vec3::splat(1.0 + JUMP_SCALE * (PI * t / JUMP_SECS).sin()),
+        Stance::Crouched(_) => Vec3::new(1.1, 0.7, 1.0),
+    };
+    transform.scale = Vec3::splat(resolution.scale()) * factor;
+}
+
+pub(super) fn reset_stance(
+    resolution: Res<Resolution>,
+    mut stance: ResMut<Stance>,
+    mut transform: Single<&mut Transform, With<LuigeeSprite>>,
+) {
+    *stance = Stance::default();
+    transform.scale = Vec3::splat(resolution.scale());
+}
@@ -2,14 +2,23 @@ use bevy::prelude::*;
 
 use crate::{
     GameState, LugeState, Resolution,
+    leaderboard::format_time,
     loading::{FontAssets, SpriteAssets},
-    player::PlayerStats,
+    player::{PlayerStats, StatKind, Wallet},
+    save::SaveData,
     ui::{ButtonColors, ChangeLugeState, UiColor},
 };
 
-use super::RunTimer;
+use super::daily::{DAILY_STATS, DailyChallenge};
 use super::dialogue::{DialogueState, RickDialogue, RickLines};
-use super::spawner::PlayerCoins;
+use super::ghost::GhostGap;
+use super::powerups::{ActivePowerUps, PowerUp};
+use super::replay::StartReplay;
+use super::rng::RunSeed;
+use super::score::RunScore;
+use super::shop::BuyStat;
+use super::spawner::{Combo, PlayerCoins};
+use super::{Durability, RunDistance, RunTimer};
 
 #[derive(Component)]
 pub(super) struct DialogueHint;
@@ -17,9 +26,56 @@ pub(super) struct DialogueHint;
 #[derive(Component)]
 pub(super) struct RunTimerText;
 
+#[derive(Component)]
+pub(super) struct DistanceText;
+
+#[derive(Component)]
+pub(super) struct ScoreText;
+
 #[derive(Component)]
 pub(super) struct CoinCountText;
 
+#[derive(Component)]
+pub(super) struct ComboText;
+
+// time left on one power-up
+#[derive(Component)]
+pub(super) struct PowerUpText(PowerUp);
+
+#[derive(Component)]
+pub(super) struct WalletText;
+
+#[derive(Component)]
+pub(super) struct DurabilityText;
+
+#[derive(Component)]
+pub(super) struct SeedText;
+
+#[derive(Component)]
+pub(super) struct GhostGapText;
+
+#[derive(Component)]
+pub(super) struct DailyText;
+
+// buttons that only work between runs
+#[derive(Component)]
+pub(super) struct LoadoutButton;
+
+#[derive(Component, Default)]
+pub(super) struct SeedField {
+    digits: String,
+    editing: bool,
+}
+
+#[derive(Component)]
+pub(super) struct SeedFieldText;
+
+#[derive(Component)]
+pub(super) struct StatText(StatKind);
+
+#[derive(Component)]
+pub(super) struct StatPriceText(StatKind);
+
 pub(super) fn spawn_slick_ui(
     mut commands: Commands,
     resolution: Res<Resolution>,
@@ -115,11 +171,16 @@ pub(super) fn spawn_slick_ui(
         });
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn spawn_luigee_ui(
     mut commands: Commands,
     resolution: Res<Resolution>,
     fonts: Res<FontAssets>,
     player_stats: Res<PlayerStats>,
+    wallet: Res<Wallet>,
+    durability: Res<Durability>,
+    save: Res<SaveData>,
+    daily: Option<Res<DailyChallenge>>,
 ) {
     let s = resolution.ui_scale();
     let border = 8.0 * s;
@@ -165,15 +226,121 @@ pub(super) fn spawn_luigee_ui(
                         },
                     ));
                     let font_size = 36.0 * s;
-                    let stats = [
-                        format!("ATK: {}", player_stats.attack),
-                        format!("DEF: {}", player_stats.defence),
-                        format!("SPD: {}", player_stats.speed),
-                        format!("LCK: {}", player_stats.luck),
-                    ];
-                    for stat in stats {
+                    let button_colors = ButtonColors::default();
+                    let shown = shown_stats(&player_stats, daily.is_some());
+                    for kind in StatKind::ALL {
+                        stats_parent
+                            .spawn(Node {
+                                width: Val::Percent(100.0),
+                                flex_direction: FlexDirection::Row,
+                                justify_content: JustifyContent::SpaceBetween,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            })
+                            .with_children(|row| {
+                                row.spawn((
+                                    StatText(kind),
+                                    Text::new(stat_label(shown, kind)),
+                                    TextFont {
+                                        font: font.clone(),
+                                        font_size,
+                                        ..default()
+                                    },
+                                ));
+                                row.spawn((
+                                    Name::new("Buy Stat Button"),
+                                    Button,
+                                    BuyStat(kind),
+                                    LoadoutButton,
+                                    Visibility::Hidden,
+                                    button_colors.clone(),
+                                    Node {
+                                        padding: UiRect::axes(Val::Px(8.0 * s), Val::Px(2.0 * s)),
+                                        border: UiRect::all(Val::Px(2.0 * s)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(button_colors.normal),
+                                    BorderColor::all(UiColor::Darkest.color()),
+                                ))
+                                .with_child((
+                                    StatPriceText(kind),
+                                    Text::new(price_label(&player_stats, kind)),
+                                    TextFont {
+                                        font: font.clone(),
+                                        font_size: 18.0 * s,
+                                        ..default()
+                                    },
+                                    TextColor(UiColor::Darkest.color()),
+                                ));
+                            });
+                    }
+                    stats_parent.spawn((
+                        DurabilityText,
+                        Text::new(format!("HP: {}/{}", durability.current, durability.max)),
+                        TextFont {
+                            font: font.clone(),
+                            font_size,
+                            ..default()
+                        },
+                    ));
+                    stats_parent.spawn((
+                        RunTimerText,
+                        Text::new("00:00.00"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size,
+                            ..default()
+                        },
+                    ));
+                    stats_parent.spawn((
+                        DistanceText,
+                        Text::new("0m"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size,
+                            ..default()
+                        },
+                    ));
+                    stats_parent.spawn((
+                        GhostGapText,
+                        Text::new("Ghost: -"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size,
+                            ..default()
+                        },
+                    ));
+                    stats_parent.spawn((
+                        ScoreText,
+                        Text::new("Score: 0"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size,
+                            ..default()
+                        },
+                    ));
+                    stats_parent.spawn((
+                        CoinCountText,
+                        Text::new("Coins: 0"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size,
+                            ..default()
+                        },
+                    ));
+                    stats_parent.spawn((
+                        ComboText,
+                        Text::new(combo_label(&Combo::default())),
+                        TextFont {
+                            font: font.clone(),
+                            font_size,
+                            ..default()
+                        },
+                    ));
+                    for power_up in PowerUp::ALL {
                         stats_parent.spawn((
-                            Text::new(stat),
+                            PowerUpText(power_up),
+                            Text::new(power_up_label(power_up, None)),
                             TextFont {
                                 font: font.clone(),
                                 font_size,
@@ -182,17 +349,28 @@ pub(super) fn spawn_luigee_ui(
                         ));
                     }
                     stats_parent.spawn((
-                        RunTimerText,
-                        Text::new("00:00.00"),
+                        SeedText,
+                        Text::new("Seed: -"),
                         TextFont {
                             font: font.clone(),
                             font_size,
                             ..default()
                         },
                     ));
+                    if let Some(daily) = &daily {
+                        stats_parent.spawn((
+                            DailyText,
+                            Text::new(daily_label(daily, &save)),
+                            TextFont {
+                                font: font.clone(),
+                                font_size,
+                                ..default()
+                            },
+                        ));
+                    }
                     stats_parent.spawn((
-                        CoinCountText,
-                        Text::new("Coins: 0"),
+                        WalletText,
+                        Text::new(format!("Wallet: {}", **wallet)),
                         TextFont {
                             font: font.clone(),
                             font_size,
@@ -207,14 +385,67 @@ pub(super) fn spawn_luigee_ui(
                     Node {
                         width: Val::Percent(100.0),
                         height: Val::Percent(35.0),
+                        flex_direction: FlexDirection::Column,
                         justify_content: JustifyContent::Center,
                         align_items: AlignItems::Center,
+                        row_gap: Val::Px(border),
                         border: UiRect::all(Val::Px(border)),
                         ..default()
                     },
                     BorderColor::all(UiColor::Darker.color()),
                 ))
                 .with_children(|btn_parent| {
+                    btn_parent
+                        .spawn((
+                            Name::new("Seed Field"),
+                            Button,
+                            SeedField::default(),
+                            LoadoutButton,
+                            Visibility::Hidden,
+                            ButtonColors::default(),
+                            Node {
+                                padding: UiRect::axes(Val::Px(12.0 * s), Val::Px(4.0 * s)),
+                                border: UiRect::all(Val::Px(2.0 * s)),
+                                ..default()
+                            },
+                            BackgroundColor(UiColor::Light.color()),
+                            BorderColor::all(UiColor::Darkest.color()),
+                        ))
+                        .with_child((
+                            SeedFieldText,
+                            Text::new(seed_field_label(&SeedField::default())),
+                            TextFont {
+                                font: fonts.tiny5.clone(),
+                                font_size: 18.0 * s,
+                                ..default()
+                            },
+                            TextColor(UiColor::Darkest.color()),
+                        ));
+                    btn_parent
+                        .spawn((
+                            Name::new("Replay Button"),
+                            Button,
+                            StartReplay,
+                            LoadoutButton,
+                            Visibility::Hidden,
+                            ButtonColors::default(),
+                            Node {
+                                padding: UiRect::axes(Val::Px(12.0 * s), Val::Px(4.0 * s)),
+                                border: UiRect::all(Val::Px(2.0 * s)),
+                                ..default()
+                            },
+                            BackgroundColor(UiColor::Light.color()),
+                            BorderColor::all(UiColor::Darkest.color()),
+                        ))
+                        .with_child((
+                            Text::new("Replay last run"),
+                            TextFont {
+                                font: fonts.tiny5.clone(),
+                                font_size: 18.0 * s,
+                                ..default()
+                            },
+                            TextColor(UiColor::Darkest.color()),
+                        ));
                     btn_parent
                         .spawn((
                             Name::new("Launch Button"),
@@ -259,15 +490,172 @@ pub(super) fn toggle_launch_button(
     }
 }
 
+pub(super) fn toggle_loadout_buttons(
+    dialogue_state: Res<DialogueState>,
+    mut buttons: Query<&mut Visibility, With<LoadoutButton>>,
+) {
+    let visibility = if dialogue_state.waiting_for_input {
+        Visibility::Hidden
+    } else {
+        Visibility::Visible
+    };
+    for mut button in &mut buttons {
+        *button = visibility;
+    }
+}
+
+// the shop and seed field are closed while Luigee is out on the luge
+pub(super) fn hide_loadout_buttons(mut buttons: Query<&mut Visibility, With<LoadoutButton>>) {
+    for mut button in &mut buttons {
+        *button = Visibility::Hidden;
+    }
+}
+
+// u64::MAX has 20 digits, stay below it so any typed seed parses
+const MAX_SEED_DIGITS: usize = 19;
+
+fn seed_field_label(field: &SeedField) -> String {
+    match (field.digits.is_empty(), field.editing) {
+        (_, true) => format!("Seed: {}_", field.digits),
+        (true, false) => "Seed: random".to_string(),
+        (false, false) => format!("Seed: {}", field.digits),
+    }
+}
+
+fn seed_digit(key: KeyCode) -> Option<char> {
+    use KeyCode::*;
+    let digit = match key {
+        Digit0 | Numpad0 => '0',
+        Digit1 | Numpad1 => '1',
+        Digit2 | Numpad2 => '2',
+        Digit3 | Numpad3 => '3',
+        Digit4 | Numpad4 => '4',
+        Digit5 | Numpad5 => '5',
+        Digit6 | Numpad6 => '6',
+        Digit7 | Numpad7 => '7',
+        Digit8 | Numpad8 => '8',
+        Digit9 | Numpad9 => '9',
+        _ => return None,
+    };
+    Some(digit)
+}
+
+// click the seed field to type a seed, leave it empty for a random one
+pub(super) fn edit_seed(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut run_seed: ResMut<RunSeed>,
+    field: Single<(Ref<Interaction>, &mut SeedField)>,
+    mut label: Single<&mut Text, With<SeedFieldText>>,
+) {
+    let (interaction, mut field) = field.into_inner();
+
+    if interaction.is_changed() && *interaction == Interaction::Pressed {
+        field.editing = !field.editing;
+    }
+
+    if field.editing {
+        for key in keyboard.get_just_pressed() {
+            match key {
+                KeyCode::Backspace => {
+                    field.digits.pop();
+                }
+                KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Escape => {
+                    field.editing = false;
+                }
+                _ => {
+                    if let Some(digit) = seed_digit(*key)
+                        && field.digits.len() < MAX_SEED_DIGITS
+                    {
+                        field.digits.push(digit);
+                    }
+                }
+            }
+        }
+    }
+
+    if field.is_changed() {
+        run_seed.requested = field.digits.parse().ok();
+        label.0 = seed_field_label(&field);
+    }
+}
+
+pub(super) fn update_seed_text(
+    run_seed: Res<RunSeed>,
+    mut query: Query<&mut Text, With<SeedText>>,
+) {
+    if run_seed.is_changed() {
+        for mut text in &mut query {
+            **text = format!("Seed: {}", run_seed.current);
+        }
+    }
+}
+
 pub(super) fn update_run_timer_text(
     timer: Res<RunTimer>,
     mut query: Query<&mut Text, With<RunTimerText>>,
 ) {
-    let elapsed = timer.0.elapsed_secs();
-    let minutes = (elapsed / 60.0) as u32;
-    let seconds = elapsed % 60.0;
+    let elapsed = format_time(timer.0.elapsed_secs());
+    for mut text in &mut query {
+        **text = elapsed.clone();
+    }
+}
+
+pub(super) fn update_distance_text(
+    distance: Res<RunDistance>,
+    score: Res<RunScore>,
+    mut distance_text: Query<&mut Text, With<DistanceText>>,
+    mut score_text: Query<&mut Text, (With<ScoreText>, Without<DistanceText>)>,
+) {
+    for mut text in &mut distance_text {
+        **text = format!("{:.0}m", **distance);
+    }
+    if score.is_changed() {
+        for mut text in &mut score_text {
+            **text = format!("Score: {}", **score);
+        }
+    }
+}
+
+pub(super) fn update_ghost_gap_text(
+    gap: Res<GhostGap>,
+    mut query: Query<&mut Text, With<GhostGapText>>,
+) {
+    if !gap.is_changed() {
+        return;
+    }
+    let label = match **gap {
+        Some(ahead) => format!("Ghost: {:+.0}m", ahead),
+        None => "Ghost: -".to_string(),
+    };
     for mut text in &mut query {
-        **text = format!("{:02}:{:05.2}", minutes, seconds);
+        **text = label.clone();
+    }
+}
+
+fn daily_label(daily: &DailyChallenge, save: &SaveData) -> String {
+    let record = &save.daily;
+    if record.attempted(daily.date) {
+        format!(
+            "Daily: {} (practice) Best: {}",
+            record.last_score, record.best_score
+        )
+    } else {
+        format!("Daily: scored run Best: {}", record.best_score)
+    }
+}
+
+pub(super) fn update_daily_text(
+    save: Res<SaveData>,
+    daily: Option<Res<DailyChallenge>>,
+    mut query: Query<&mut Text, With<DailyText>>,
+) {
+    let Some(daily) = daily else {
+        return;
+    };
+    if save.is_changed() {
+        for mut text in &mut query {
+            **text = daily_label(&daily, &save);
+        }
     }
 }
 
@@ -281,3 +669,86 @@ pub(super) fn update_coin_count_text(
         }
     }
 }
+
+fn combo_label(combo: &Combo) -> String {
+    format!("Combo: {} x{}", combo.streak, combo.multiplier())
+}
+
+pub(super) fn update_combo_text(combo: Res<Combo>, mut query: Query<&mut Text, With<ComboText>>) {
+    if combo.is_changed() {
+        for mut text in &mut query {
+            **text = combo_label(&combo);
+        }
+    }
+}
+
+fn power_up_label(power_up: PowerUp, remaining: Option<f32>) -> String {
+    match remaining {
+        Some(secs) => format!("{}: {:.1}s", power_up.label(), secs),
+        None => format!("{}: -", power_up.label()),
+    }
+}
+
+pub(super) fn update_power_up_texts(
+    active: Res<ActivePowerUps>,
+    mut query: Query<(&mut Text, &PowerUpText)>,
+) {
+    if active.is_changed() {
+        for (mut text, power_up) in &mut query {
+            **text = power_up_label(power_up.0, active.remaining(power_up.0));
+        }
+    }
+}
+
+pub(super) fn update_wallet_text(
+    wallet: Res<Wallet>,
+    mut query: Query<&mut Text, With<WalletText>>,
+) {
+    if wallet.is_changed() {
+        for mut text in &mut query {
+            **text = format!("Wallet: {}", **wallet);
+        }
+    }
+}
+
+pub(super) fn update_durability_text(
+    durability: Res<Durability>,
+    mut query: Query<&mut Text, With<DurabilityText>>,
+) {
+    if durability.is_changed() {
+        for mut text in &mut query {
+            **text = format!("HP: {}/{}", durability.current, durability.max);
+        }
+    }
+}
+
+// the daily challenge runs on a fixed loadout, so show that instead of the player's upgrades
+fn shown_stats(player_stats: &PlayerStats, daily: bool) -> &PlayerStats {
+    if daily { &DAILY_STATS } else { player_stats }
+}
+
+fn stat_label(player_stats: &PlayerStats, kind: StatKind) -> String {
+    format!("{}: {}", kind.label(), player_stats.get(kind))
+}
+
+fn price_label(player_stats: &PlayerStats, kind: StatKind) -> String {
+    format!("+ {}c", player_stats.upgrade_price(kind))
+}
+
+pub(super) fn update_stat_texts(
+    player_stats: Res<PlayerStats>,
+    daily: Option<Res<DailyChallenge>>,
+    mut stat_texts: Query<(&mut Text, &StatText)>,
+    mut price_texts: Query<(&mut Text, &StatPriceText), Without<StatText>>,
+) {
+    if !player_stats.is_changed() {
+        return;
+    }
+    let shown = shown_stats(&player_stats, daily.is_some());
+    for (mut text, stat) in &mut stat_texts {
+        **text = stat_label(shown, stat.0);
+    }
+    for (mut text, price) in &mut price_texts {
+        **text = price_label(&player_stats, price.0);
+    }
+}
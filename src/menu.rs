@@ -1,4 +1,5 @@
 use crate::loading::{FontAssets, SpriteAssets, TextureAssets};
+use crate::luge::StartDaily;
 use crate::ui::{ButtonColors, ChangeState, OpenLink, UiColor, font_size_for};
 use crate::{GameState, Resolution};
 use bevy::prelude::*;
@@ -36,7 +37,9 @@ fn setup_menu(
 
     // font sizes derived from button dimensions + text length
     let play_font = font_size_for(btn_w, btn_h, "Play");
+    let daily_font = font_size_for(btn_w, btn_h, "Daily");
     let settings_font = font_size_for(btn_w, btn_h, "Settings");
+    let scores_font = font_size_for(btn_w, btn_h, "Scores");
     let footer_text_w = footer_w - icon_size;
     let footer_font = font_size_for(footer_text_w, footer_h, "Made with Bevy");
 
@@ -93,6 +96,33 @@ fn setup_menu(
                     },
                     TextColor(UiColor::Darkest.color()),
                 ));
+            children
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(btn_w),
+                        height: Val::Px(btn_h),
+                        border: UiRect::all(Val::Px(border)),
+                        padding: UiRect::axes(Val::Px(pad_x), Val::Px(pad_y)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    BorderColor::all(UiColor::Darkest.color()),
+                    BackgroundColor(button_colors.normal),
+                    button_colors.clone(),
+                    ChangeState(GameState::Playing),
+                    StartDaily,
+                ))
+                .with_child((
+                    Text::new("Daily"),
+                    TextFont {
+                        font: fonts.tiny5.clone(),
+                        font_size: daily_font,
+                        ..default()
+                    },
+                    TextColor(UiColor::Darkest.color()),
+                ));
             children
                 .spawn((
                     Button,
@@ -119,6 +149,32 @@ fn setup_menu(
                     },
                     TextColor(UiColor::Darkest.color()),
                 ));
+            children
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(btn_w),
+                        height: Val::Px(btn_h),
+                        border: UiRect::all(Val::Px(border)),
+                        padding: UiRect::axes(Val::Px(pad_x), Val::Px(pad_y)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    BorderColor::all(UiColor::Darkest.color()),
+                    BackgroundColor(button_colors.normal),
+                    button_colors.clone(),
+                    ChangeState(GameState::Leaderboard),
+                ))
+                .with_child((
+                    Text::new("Scores"),
+                    TextFont {
+                        font: fonts.tiny5.clone(),
+                        font_size: scores_font,
+                        ..default()
+                    },
+                    TextColor(UiColor::Darkest.color()),
+                ));
         });
     commands
         .spawn((
@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::InputMap;
+use serde::{Deserialize, Serialize};
 
-use crate::actions::GameAction;
+use crate::actions::{GameAction, KeyBindings};
 
 pub struct PlayerPlugin;
 
@@ -11,15 +12,15 @@ impl Plugin for PlayerPlugin {
     }
 
     fn build(&self, app: &mut App) {
-        app.insert_resource(PlayerStats::default());
+        app.insert_resource(PlayerStats::default())
+            .insert_resource(Wallet::default());
     }
 }
 
 #[derive(Component)]
 pub struct Player;
 
-#[allow(dead_code)]
-#[derive(Resource)]
+#[derive(Resource, Clone, Serialize, Deserialize)]
 pub struct PlayerStats {
     pub attack: i32,
     pub defence: i32,
@@ -27,6 +28,10 @@ pub struct PlayerStats {
     pub luck: i32,
 }
 
+// Slick Coins banked across runs, spent in Rick's shop
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct Wallet(pub u32);
+
 impl Default for PlayerStats {
     fn default() -> Self {
         Self {
@@ -38,20 +43,71 @@ impl Default for PlayerStats {
     }
 }
 
+impl PlayerStats {
+    pub fn get(&self, kind: StatKind) -> i32 {
+        use StatKind::*;
+        match kind {
+            Attack => self.attack,
+            Defence => self.defence,
+            Speed => self.speed,
+            Luck => self.luck,
+        }
+    }
+
+    pub fn get_mut(&mut self, kind: StatKind) -> &mut i32 {
+        use StatKind::*;
+        match kind {
+            Attack => &mut self.attack,
+            Defence => &mut self.defence,
+            Speed => &mut self.speed,
+            Luck => &mut self.luck,
+        }
+    }
+
+    // price of the next level, grows quadratically with the current level
+    pub fn upgrade_price(&self, kind: StatKind) -> u32 {
+        let level = self.get(kind).max(1) as u32;
+        StatKind::BASE_PRICE * level * level
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StatKind {
+    Attack,
+    Defence,
+    Speed,
+    Luck,
+}
+
+impl StatKind {
+    pub const ALL: [Self; 4] = [Self::Attack, Self::Defence, Self::Speed, Self::Luck];
+
+    const BASE_PRICE: u32 = 10;
+
+    pub fn label(&self) -> &'static str {
+        use StatKind::*;
+        match self {
+            Attack => "ATK",
+            Defence => "DEF",
+            Speed => "SPD",
+            Luck => "LCK",
+        }
+    }
+}
+
 impl Player {
-    pub fn default_input_map() -> InputMap<GameAction> {
+    pub fn input_map(bindings: &KeyBindings) -> InputMap<GameAction> {
         use GameAction::*;
 
         let mut input_map = InputMap::default();
 
-        // Movement
-        input_map.insert(Left, KeyCode::ArrowLeft);
-        input_map.insert(Right, KeyCode::ArrowRight);
-        input_map.insert(Brake, KeyCode::ArrowDown);
+        for (action, key) in &bindings.0 {
+            input_map.insert(*action, *key);
+        }
 
         // Dialogue
-        input_map.insert(Continue, KeyCode::Space);
         input_map.insert(Continue, MouseButton::Left);
+        input_map.insert(Pause, GamepadButton::Start);
 
         input_map
     }
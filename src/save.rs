@@ -0,0 +1,247 @@
+use std::io;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    luge::{DailyRecord, SceneId},
+    player::{PlayerStats, Wallet},
+};
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn name(&self) -> &str {
+        "Save Plugin"
+    }
+
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SaveBackend::platform_default())
+            .init_resource::<SaveData>()
+            .add_systems(PreStartup, load_save)
+            .add_systems(Last, write_save.run_if(resource_exists::<SaveRequested>));
+    }
+}
+
+const SAVE_VERSION: u32 = 1;
+const SAVE_KEY: &str = "save.ron";
+
+// everything that survives a restart, mirrored from the live resources
+#[derive(Resource, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SaveData {
+    pub version: u32,
+    pub stats: PlayerStats,
+    pub wallet: u32,
+    pub completed_scenes: Vec<SceneId>,
+    pub daily: DailyRecord,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            version: SAVE_VERSION,
+            stats: PlayerStats::default(),
+            wallet: 0,
+            completed_scenes: Vec::new(),
+            daily: DailyRecord::default(),
+        }
+    }
+}
+
+// insert to have the save written at the end of the frame
+#[derive(Resource)]
+pub struct SaveRequested;
+
+// where save files live, swapped out per platform
+pub trait SaveStorage: Send + Sync + 'static {
+    fn read(&self, key: &str) -> io::Result<Option<String>>;
+    fn write(&self, key: &str, contents: &str) -> io::Result<()>;
+}
+
+#[derive(Resource, Deref)]
+pub struct SaveBackend(Box<dyn SaveStorage>);
+
+impl SaveBackend {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn platform_default() -> Self {
+        Self(Box::new(FileStorage::new()))
+    }
+
+    // the web build keeps saves in memory until it gets its own backend
+    #[cfg(target_arch = "wasm32")]
+    fn platform_default() -> Self {
+        Self(Box::new(MemoryStorage::default()))
+    }
+
+    // reads and parses a file, logging and returning None on any failure
+    pub fn load<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let contents = match self.read(key) {
+            Ok(Some(contents)) => contents,
+            Ok(None) => {
+                info!("No {key} found, starting fresh");
+                return None;
+            }
+            Err(error) => {
+                warn!("Failed to read {key} {error:?}");
+                return None;
+            }
+        };
+
+        match ron::from_str(&contents) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                warn!("Failed to parse {key} {error:?}");
+                None
+            }
+        }
+    }
+
+    pub fn store<T: Serialize>(&self, key: &str, value: &T) {
+        let contents = match ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(error) => {
+                warn!("Failed to serialize {key} {error:?}");
+                return;
+            }
+        };
+
+        if let Err(error) = self.write(key, &contents) {
+            warn!("Failed to write {key} {error:?}");
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileStorage {
+    // None when the platform has nowhere to keep app data
+    dir: Option<std::path::PathBuf>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+    fn new() -> Self {
+        let dir = data_dir().map(|dir| dir.join("slick_ricks_luge_lounge"));
+        match &dir {
+            Some(dir) => info!("Saving to {}", dir.display()),
+            None => warn!("No data directory found, progress won't be saved"),
+        }
+        Self { dir }
+    }
+
+    fn path(&self, key: &str) -> io::Result<std::path::PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(key)).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "no data directory on this platform",
+            )
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SaveStorage for FileStorage {
+    fn read(&self, key: &str) -> io::Result<Option<String>> {
+        match std::fs::read_to_string(self.path(key)?) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn write(&self, key: &str, contents: &str) -> io::Result<()> {
+        let path = self.path(key)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // write to a temp file first so a crash can't leave half a save behind
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, contents)?;
+        std::fs::rename(tmp, path)
+    }
+}
+
+// the app's private storage, an android process has no home directory
+#[cfg(target_os = "android")]
+fn data_dir() -> Option<std::path::PathBuf> {
+    bevy::android::ANDROID_APP
+        .get()
+        .and_then(|app| app.internal_data_path())
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+fn data_dir() -> Option<std::path::PathBuf> {
+    use std::{env, path::PathBuf};
+
+    let home = || env::var_os("HOME").map(PathBuf::from);
+    if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(any(target_os = "macos", target_os = "ios")) {
+        // on ios HOME is the app's sandbox container
+        home().map(|h| h.join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home().map(|h| h.join(".local/share")))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[derive(Default)]
+pub struct MemoryStorage(std::sync::Mutex<std::collections::HashMap<String, String>>);
+
+#[cfg(target_arch = "wasm32")]
+impl SaveStorage for MemoryStorage {
+    fn read(&self, key: &str) -> io::Result<Option<String>> {
+        Ok(self.0.lock().unwrap().get(key).cloned())
+    }
+
+    fn write(&self, key: &str, contents: &str) -> io::Result<()> {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), contents.to_string());
+        Ok(())
+    }
+}
+
+fn load_save(
+    backend: Res<SaveBackend>,
+    mut save: ResMut<SaveData>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut wallet: ResMut<Wallet>,
+) {
+    let Some(loaded) = backend.load::<SaveData>(SAVE_KEY) else {
+        return;
+    };
+
+    if loaded.version > SAVE_VERSION {
+        warn!(
+            "Save version {} is newer than supported version {}, ignoring it",
+            loaded.version, SAVE_VERSION
+        );
+        return;
+    }
+
+    *player_stats = loaded.stats.clone();
+    **wallet = loaded.wallet;
+    *save = SaveData {
+        version: SAVE_VERSION,
+        ..loaded
+    };
+    info!("Loaded save");
+}
+
+fn write_save(
+    mut commands: Commands,
+    backend: Res<SaveBackend>,
+    mut save: ResMut<SaveData>,
+    player_stats: Res<PlayerStats>,
+    wallet: Res<Wallet>,
+) {
+    commands.remove_resource::<SaveRequested>();
+
+    save.stats = player_stats.clone();
+    save.wallet = **wallet;
+    backend.store(SAVE_KEY, &*save);
+}
@@ -1,8 +1,11 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     GameState, Resolution,
+    actions::KeyBindings,
     loading::FontAssets,
+    save::SaveBackend,
     ui::{ButtonColors, ChangeState, UiColor},
 };
 
@@ -15,7 +18,9 @@ impl Plugin for SettingsPlugin {
 
     fn build(&self, app: &mut App) {
         app.init_resource::<Resolution>();
-        app.add_systems(Startup, set_window_resolution)
+        app.init_resource::<Settings>();
+        app.add_systems(PreStartup, load_settings)
+            .add_systems(Startup, set_window_resolution)
             .add_systems(OnEnter(GameState::Settings), spawn_settings_menu)
             .add_systems(
                 Update,
@@ -24,6 +29,95 @@ impl Plugin for SettingsPlugin {
     }
 }
 
+const SETTINGS_VERSION: u32 = 1;
+const SETTINGS_KEY: &str = "settings.ron";
+
+// player preferences, kept in their own file apart from the save game
+#[derive(Resource, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    pub resolution: Resolution,
+    pub volume: AudioVolume,
+    pub bindings: KeyBindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            resolution: Resolution::default(),
+            volume: AudioVolume::default(),
+            bindings: KeyBindings::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn store(&self, backend: &SaveBackend) {
+        backend.store(SETTINGS_KEY, self);
+    }
+}
+
+// linear volumes from 0.0 to 1.0, music and sfx are scaled by master
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct AudioVolume {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+#[derive(Clone, Copy)]
+pub enum VolumeChannel {
+    Master,
+    Music,
+    Sfx,
+}
+
+impl VolumeChannel {
+    pub const ALL: [Self; 3] = [Self::Master, Self::Music, Self::Sfx];
+
+    pub fn label(&self) -> &'static str {
+        use VolumeChannel::*;
+        match self {
+            Master => "Master",
+            Music => "Music",
+            Sfx => "Sfx",
+        }
+    }
+}
+
+impl AudioVolume {
+    pub fn get(&self, channel: VolumeChannel) -> f32 {
+        use VolumeChannel::*;
+        match channel {
+            Master => self.master,
+            Music => self.music,
+            Sfx => self.sfx,
+        }
+    }
+
+    pub fn get_mut(&mut self, channel: VolumeChannel) -> &mut f32 {
+        use VolumeChannel::*;
+        match channel {
+            Master => &mut self.master,
+            Music => &mut self.music,
+            Sfx => &mut self.sfx,
+        }
+    }
+}
+
+impl Default for AudioVolume {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 0.8,
+            sfx: 0.8,
+        }
+    }
+}
+
 // marker components
 #[derive(Component)]
 pub struct DropdownHead;
@@ -37,6 +131,32 @@ pub struct DropdownPanel;
 #[derive(Component)]
 pub struct DropdownChoice(pub Resolution);
 
+fn load_settings(
+    backend: Res<SaveBackend>,
+    mut settings: ResMut<Settings>,
+    mut resolution: ResMut<Resolution>,
+) {
+    let Some(loaded) = backend.load::<Settings>(SETTINGS_KEY) else {
+        return;
+    };
+
+    if loaded.version > SETTINGS_VERSION {
+        warn!(
+            "Settings version {} is newer than supported version {}, ignoring them",
+            loaded.version, SETTINGS_VERSION
+        );
+        return;
+    }
+
+    *settings = Settings {
+        version: SETTINGS_VERSION,
+        bindings: loaded.bindings.with_defaults(),
+        ..loaded
+    };
+    *resolution = settings.resolution;
+    info!("Loaded settings");
+}
+
 fn set_window_resolution(mut window: Single<&mut Window>, resolution: Res<Resolution>) {
     let res = resolution.vec2();
     window.resolution.set(res.x, res.y);
@@ -234,19 +354,23 @@ fn toggle_dropdown(
 }
 
 fn select_option(
+    backend: Res<SaveBackend>,
     choice_query: Query<(&Interaction, &DropdownChoice), Changed<Interaction>>,
     mut label: Single<&mut Text, With<DropdownLabel>>,
     mut panel: Single<&mut Node, With<DropdownPanel>>,
+    mut settings: ResMut<Settings>,
     mut resolution: ResMut<Resolution>,
     mut window: Single<&mut Window>,
 ) {
     for (interaction, choice) in &choice_query {
         if *interaction == Interaction::Pressed {
+            settings.resolution = choice.0;
             *resolution = choice.0;
             let res = choice.0.vec2();
             window.resolution.set(res.x, res.y);
             label.0 = choice.0.label().to_string();
             panel.display = Display::None;
+            settings.store(&backend);
         }
     }
 }
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{GameState, LugeState};
+use crate::{GameState, LugeState, RunState};
 
 pub struct UiPlugin;
 
@@ -11,6 +11,7 @@ impl Plugin for UiPlugin {
             button_click_handler.run_if(
                 in_state(GameState::Menu)
                     .or(in_state(GameState::Settings))
+                    .or(in_state(GameState::Leaderboard))
                     .or(in_state(GameState::Playing)),
             ),
         );
@@ -65,6 +66,9 @@ pub struct OpenLink(pub &'static str);
 #[derive(Component)]
 pub struct ChangeLugeState(pub LugeState);
 
+#[derive(Component)]
+pub struct ChangeRunState(pub RunState);
+
 // helper method to make fonts fit
 pub fn font_size_for(width: f32, height: f32, text: &str) -> f32 {
     let from_height = height * 0.7;
@@ -75,6 +79,7 @@ pub fn font_size_for(width: f32, height: f32, text: &str) -> f32 {
 pub fn button_click_handler(
     mut next_state: ResMut<NextState<GameState>>,
     mut next_luge_state: ResMut<NextState<LugeState>>,
+    mut next_run_state: ResMut<NextState<RunState>>,
     mut interaction_query: Query<
         (
             &Interaction,
@@ -83,12 +88,20 @@ pub fn button_click_handler(
             Option<&ChangeState>,
             Option<&OpenLink>,
             Option<&ChangeLugeState>,
+            Option<&ChangeRunState>,
         ),
         (Changed<Interaction>, With<Button>),
     >,
 ) {
-    for (interaction, mut color, button_colors, change_state, open_link, change_luge_state) in
-        &mut interaction_query
+    for (
+        interaction,
+        mut color,
+        button_colors,
+        change_state,
+        open_link,
+        change_luge_state,
+        change_run_state,
+    ) in &mut interaction_query
     {
         match *interaction {
             Interaction::Pressed => {
@@ -100,6 +113,8 @@ pub fn button_click_handler(
                     warn!("Failed to open link {error:?}");
                 } else if let Some(state) = change_luge_state {
                     next_luge_state.set(state.0.clone());
+                } else if let Some(state) = change_run_state {
+                    next_run_state.set(state.0.clone());
                 }
             }
             Interaction::Hovered => {